@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// One line of a JSONL batch file.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BatchEntry {
+    pub text: String,
+}
+
+pub fn parse_entries(input: &str) -> Result<Vec<BatchEntry>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("invalid batch entry on line {}", i + 1))
+        })
+        .collect()
+}
+
+const MIN_DELAY: Duration = Duration::from_millis(0);
+const BACKOFF_FLOOR: Duration = Duration::from_secs(1);
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Adaptive per-method delay: doubles on rate-limit responses, decays on success.
+#[derive(Debug, Default)]
+pub struct Pacer {
+    delays: HashMap<&'static str, Duration>,
+}
+
+impl Pacer {
+    pub fn delay(&self, method: &'static str) -> Duration {
+        self.delays.get(method).copied().unwrap_or(MIN_DELAY)
+    }
+
+    pub fn on_rate_limited(&mut self, method: &'static str) -> Duration {
+        let next = (self.delay(method) * 2).clamp(BACKOFF_FLOOR, MAX_DELAY);
+        self.delays.insert(method, next);
+        next
+    }
+
+    pub fn on_success(&mut self, method: &'static str) {
+        let next = self.delay(method).mul_f64(0.9);
+        self.delays.insert(method, next.max(MIN_DELAY));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METHOD: &str = "chat.postMessage";
+
+    #[test]
+    fn parse_entries_skips_blank_lines() {
+        let entries = parse_entries("{\"text\":\"a\"}\n\n{\"text\":\"b\"}\n").unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                BatchEntry {
+                    text: "a".to_string()
+                },
+                BatchEntry {
+                    text: "b".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_entries_reports_line_number() {
+        let err = parse_entries("{\"text\":\"a\"}\nnot json\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn pacer_starts_without_delay() {
+        assert_eq!(Pacer::default().delay(METHOD), Duration::ZERO);
+    }
+
+    #[test]
+    fn pacer_backs_off_and_caps() {
+        let mut pacer = Pacer::default();
+        assert_eq!(pacer.on_rate_limited(METHOD), Duration::from_secs(1));
+        assert_eq!(pacer.on_rate_limited(METHOD), Duration::from_secs(2));
+        for _ in 0..10 {
+            pacer.on_rate_limited(METHOD);
+        }
+        assert_eq!(pacer.delay(METHOD), MAX_DELAY);
+    }
+
+    #[test]
+    fn pacer_decays_on_success() {
+        let mut pacer = Pacer::default();
+        pacer.on_rate_limited(METHOD);
+        pacer.on_success(METHOD);
+        assert!(pacer.delay(METHOD) < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn pacer_tracks_methods_separately() {
+        let mut pacer = Pacer::default();
+        pacer.on_rate_limited(METHOD);
+        assert_eq!(pacer.delay("files.completeUploadExternal"), Duration::ZERO);
+    }
+}
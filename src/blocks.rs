@@ -0,0 +1,369 @@
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+
+/// Slack's limit for header block text.
+const HEADER_MAX_CHARS: usize = 150;
+/// Slack's limit for fields in a single section block.
+const FIELDS_PER_SECTION: usize = 10;
+/// Slack's limit for section block text.
+const SECTION_MAX_CHARS: usize = 3000;
+/// Slack's limit for blocks in a single message.
+const MAX_BLOCKS: usize = 50;
+/// Slack's limit for a block_id.
+const BLOCK_ID_MAX_CHARS: usize = 255;
+
+/// Block Kit composition options collected from CLI flags.
+#[derive(Debug, Default)]
+pub struct Layout<'a> {
+    pub header: Option<&'a str>,
+    pub sections: &'a [String],
+    pub fields: &'a [String],
+    pub context: Option<&'a str>,
+    pub divider: bool,
+}
+
+impl Layout<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.header.is_none()
+            && self.sections.is_empty()
+            && self.fields.is_empty()
+            && self.context.is_none()
+            && !self.divider
+    }
+}
+
+fn parse_field(field: &str) -> Result<(&str, &str)> {
+    let (key, value) = field
+        .split_once('=')
+        .with_context(|| format!("invalid field '{field}' (expected KEY=VALUE)"))?;
+    if key.trim().is_empty() {
+        bail!("invalid field '{field}' (key must not be empty)");
+    }
+    Ok((key.trim(), value.trim()))
+}
+
+fn section(text: &str) -> Result<Value> {
+    if text.chars().count() > SECTION_MAX_CHARS {
+        bail!("section text must be at most {SECTION_MAX_CHARS} characters");
+    }
+    Ok(json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text },
+    }))
+}
+
+/// A small-print `context` block with one mrkdwn element.
+pub fn context_block(text: &str) -> Value {
+    json!({
+        "type": "context",
+        "elements": [{ "type": "mrkdwn", "text": text }],
+    })
+}
+
+/// Assemble blocks in a fixed order: header, text, sections, divider, fields, context.
+pub fn build(layout: &Layout, text: &str) -> Result<Vec<Value>> {
+    let mut blocks = Vec::new();
+
+    if let Some(header) = layout.header {
+        if header.chars().count() > HEADER_MAX_CHARS {
+            bail!("--header must be at most {HEADER_MAX_CHARS} characters");
+        }
+        blocks.push(json!({
+            "type": "header",
+            "text": { "type": "plain_text", "text": header, "emoji": true },
+        }));
+    }
+
+    if !text.is_empty() {
+        blocks.push(section(text)?);
+    }
+    for text in layout.sections {
+        blocks.push(section(text)?);
+    }
+
+    if layout.divider {
+        blocks.push(json!({ "type": "divider" }));
+    }
+
+    let fields = layout
+        .fields
+        .iter()
+        .map(|f| parse_field(f))
+        .collect::<Result<Vec<_>>>()?;
+    for chunk in fields.chunks(FIELDS_PER_SECTION) {
+        let fields: Vec<Value> = chunk
+            .iter()
+            .map(|(k, v)| json!({ "type": "mrkdwn", "text": format!("*{k}*\n{v}") }))
+            .collect();
+        blocks.push(json!({ "type": "section", "fields": fields }));
+    }
+
+    if let Some(context) = layout.context {
+        blocks.push(context_block(context));
+    }
+
+    Ok(blocks)
+}
+
+/// Parse and shape-check a raw Block Kit `blocks` array (or `{"blocks": [...]}` as exported
+/// by Block Kit Builder).
+pub fn parse(json: &str) -> Result<Vec<Value>> {
+    let value: Value = serde_json::from_str(json).context("invalid JSON in --blocks")?;
+    let blocks = match value {
+        Value::Array(blocks) => blocks,
+        Value::Object(mut obj) => match obj.remove("blocks") {
+            Some(Value::Array(blocks)) => blocks,
+            _ => bail!(
+                "--blocks must be a JSON array of blocks or an object with a \"blocks\" array"
+            ),
+        },
+        _ => bail!("--blocks must be a JSON array of blocks"),
+    };
+
+    if blocks.is_empty() {
+        bail!("--blocks must contain at least one block");
+    }
+    if blocks.len() > MAX_BLOCKS {
+        bail!(
+            "--blocks has {} blocks (Slack allows at most {MAX_BLOCKS})",
+            blocks.len()
+        );
+    }
+    for (i, block) in blocks.iter().enumerate() {
+        let Some(obj) = block.as_object() else {
+            bail!("block {i} is not an object");
+        };
+        match obj.get("type") {
+            Some(Value::String(t)) if !t.is_empty() => {}
+            _ => bail!("block {i} has no \"type\""),
+        }
+        if let Some(id) = obj.get("block_id") {
+            match id.as_str() {
+                Some(id) if id.chars().count() <= BLOCK_ID_MAX_CHARS => {}
+                _ => bail!("block {i} has an invalid block_id (string of at most {BLOCK_ID_MAX_CHARS} characters)"),
+            }
+        }
+    }
+    Ok(blocks)
+}
+
+/// Compact description of block types for the confirm prompt, e.g. `header, section×2, divider`.
+pub fn summarize(blocks: &[Value]) -> String {
+    let mut runs: Vec<(&str, usize)> = Vec::new();
+    for block in blocks {
+        let kind = block["type"].as_str().unwrap_or("?");
+        match runs.last_mut() {
+            Some((last, n)) if *last == kind => *n += 1,
+            _ => runs.push((kind, 1)),
+        }
+    }
+    runs.iter()
+        .map(|(kind, n)| match n {
+            1 => kind.to_string(),
+            n => format!("{kind}×{n}"),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Text of the first header or section block, for use as notification fallback.
+pub fn first_text(blocks: &[Value]) -> Option<&str> {
+    blocks
+        .iter()
+        .filter(|b| matches!(b["type"].as_str(), Some("header" | "section")))
+        .find_map(|b| b["text"]["text"].as_str())
+}
+
+/// Notification fallback text for a block message.
+pub fn fallback_text(layout: &Layout, text: &str) -> String {
+    if !text.is_empty() {
+        return text.to_string();
+    }
+    if let Some(header) = layout.header {
+        return header.to_string();
+    }
+    if let Some(section) = layout.sections.first() {
+        return section.clone();
+    }
+    layout
+        .fields
+        .iter()
+        .filter_map(|f| parse_field(f).ok())
+        .map(|(k, v)| format!("{k}: {v}"))
+        .chain(layout.context.map(String::from))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn build_full_layout_in_order() {
+        let fields = vec!["Version=1.2.3".to_string(), "Env = prod".to_string()];
+        let layout = Layout {
+            header: Some("Deploy complete"),
+            fields: &fields,
+            context: Some("triggered by @alice"),
+            divider: true,
+            ..Layout::default()
+        };
+        let blocks = build(&layout, "all green").unwrap();
+        let types: Vec<&str> = blocks.iter().map(|b| b["type"].as_str().unwrap()).collect();
+        assert_eq!(
+            types,
+            ["header", "section", "divider", "section", "context"]
+        );
+        assert_eq!(blocks[0]["text"]["text"], "Deploy complete");
+        assert_eq!(blocks[1]["text"]["text"], "all green");
+        assert_eq!(blocks[3]["fields"][0]["text"], "*Version*\n1.2.3");
+        assert_eq!(blocks[3]["fields"][1]["text"], "*Env*\nprod");
+        assert_eq!(blocks[4]["elements"][0]["text"], "triggered by @alice");
+    }
+
+    #[test]
+    fn build_sections_follow_text() {
+        let sections = vec!["*Changes*".to_string(), "- fix bug".to_string()];
+        let layout = Layout {
+            sections: &sections,
+            divider: true,
+            ..Layout::default()
+        };
+        let blocks = build(&layout, "intro").unwrap();
+        let texts: Vec<&str> = blocks
+            .iter()
+            .filter_map(|b| b["text"]["text"].as_str())
+            .collect();
+        assert_eq!(texts, ["intro", "*Changes*", "- fix bug"]);
+        assert_eq!(blocks[3]["type"], "divider");
+        assert_eq!(fallback_text(&layout, ""), "*Changes*");
+    }
+
+    #[test]
+    fn build_rejects_long_section() {
+        let sections = vec!["x".repeat(3001)];
+        let layout = Layout {
+            sections: &sections,
+            ..Layout::default()
+        };
+        assert!(build(&layout, "").is_err());
+    }
+
+    #[test]
+    fn build_without_text_skips_section() {
+        let layout = Layout {
+            header: Some("Hi"),
+            ..Layout::default()
+        };
+        let blocks = build(&layout, "").unwrap();
+        assert_eq!(blocks.len(), 1);
+    }
+
+    #[test]
+    fn build_chunks_fields() {
+        let fields: Vec<String> = (0..12).map(|i| format!("k{i}=v{i}")).collect();
+        let layout = Layout {
+            fields: &fields,
+            ..Layout::default()
+        };
+        let blocks = build(&layout, "").unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0]["fields"].as_array().unwrap().len(), 10);
+        assert_eq!(blocks[1]["fields"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn build_rejects_bad_field() {
+        let fields = vec!["novalue".to_string()];
+        let layout = Layout {
+            fields: &fields,
+            ..Layout::default()
+        };
+        let err = build(&layout, "").unwrap_err();
+        assert!(err.to_string().contains("expected KEY=VALUE"));
+    }
+
+    #[test]
+    fn build_rejects_long_header() {
+        let header = "x".repeat(151);
+        let layout = Layout {
+            header: Some(&header),
+            ..Layout::default()
+        };
+        assert!(build(&layout, "").is_err());
+    }
+
+    #[test]
+    fn fallback_text_prefers_text_then_header_then_fields() {
+        let fields = vec!["a=1".to_string(), "b=2".to_string()];
+        let mut layout = Layout {
+            header: Some("Head"),
+            fields: &fields,
+            ..Layout::default()
+        };
+        assert_eq!(fallback_text(&layout, "body"), "body");
+        assert_eq!(fallback_text(&layout, ""), "Head");
+        layout.header = None;
+        assert_eq!(fallback_text(&layout, ""), "a: 1, b: 2");
+    }
+
+    #[test]
+    fn parse_accepts_array_and_builder_export() {
+        let blocks = parse(r#"[{"type":"divider"}]"#).unwrap();
+        assert_eq!(blocks.len(), 1);
+        let blocks = parse(r#"{"blocks":[{"type":"divider"},{"type":"divider"}]}"#).unwrap();
+        assert_eq!(blocks.len(), 2);
+    }
+
+    #[rstest]
+    #[case("not json", "invalid JSON")]
+    #[case(r#""text""#, "must be a JSON array")]
+    #[case(r#"{"text":"x"}"#, "\"blocks\" array")]
+    #[case("[]", "at least one block")]
+    #[case("[1]", "block 0 is not an object")]
+    #[case(r#"[{"type":"divider"},{"text":"x"}]"#, "block 1 has no \"type\"")]
+    #[case(r#"[{"type":"divider","block_id":1}]"#, "invalid block_id")]
+    fn parse_rejects_bad_shape(#[case] input: &str, #[case] expected: &str) {
+        let err = parse(input).unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[test]
+    fn parse_rejects_too_many_blocks() {
+        let json = format!("[{}]", vec![r#"{"type":"divider"}"#; 51].join(","));
+        let err = parse(&json).unwrap_err();
+        assert!(err.to_string().contains("at most 50"));
+    }
+
+    #[test]
+    fn summarize_collapses_runs() {
+        let blocks = parse(
+            r#"[{"type":"header"},{"type":"section"},{"type":"section"},{"type":"divider"},{"type":"section"}]"#,
+        )
+        .unwrap();
+        assert_eq!(summarize(&blocks), "header, section×2, divider, section");
+    }
+
+    #[test]
+    fn first_text_prefers_header_or_section() {
+        let blocks = parse(
+            r#"[{"type":"divider"},{"type":"section","text":{"type":"mrkdwn","text":"hello"}}]"#,
+        )
+        .unwrap();
+        assert_eq!(first_text(&blocks), Some("hello"));
+        assert_eq!(first_text(&parse(r#"[{"type":"divider"}]"#).unwrap()), None);
+    }
+
+    #[test]
+    fn layout_is_empty() {
+        assert!(Layout::default().is_empty());
+        let layout = Layout {
+            divider: true,
+            ..Layout::default()
+        };
+        assert!(!layout.is_empty());
+    }
+}
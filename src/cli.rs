@@ -10,10 +10,30 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub profile: Option<String>,
 
+    /// Config file to use instead of the default location
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<String>,
+
     /// Run without config file (all settings from env vars)
     #[arg(long, global = true)]
     pub headless: bool,
 
+    /// Network timeout for connecting and for each Slack API call, e.g. 10s (0 = none)
+    #[arg(long, global = true, value_name = "DURATION")]
+    pub timeout: Option<String>,
+
+    /// Disable TLS certificate verification (discouraged; prefer ca_bundle in config)
+    #[arg(long, global = true)]
+    pub insecure: bool,
+
+    /// Send to the profile's test_channel instead, noting the real destination (rehearsal)
+    #[arg(long, global = true)]
+    pub redirect_to_test: bool,
+
+    /// Named destination from the profile's destinations (required when it has them)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub dest: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Command>,
 
@@ -21,32 +41,314 @@ pub struct Cli {
     pub send: SendArgs,
 }
 
-#[derive(clap::Args)]
+#[derive(Clone, clap::Args)]
 pub struct SendArgs {
     /// Text message (reads from stdin if value omitted)
     #[arg(short, long, num_args = 0..=1, default_missing_value = "")]
     pub text: Option<String>,
 
-    /// File to upload (reads from stdin if path omitted)
+    /// File to upload (reads from stdin if path omitted; repeat to send several files as one message)
     #[arg(short, long, num_args = 0..=1, default_missing_value = "")]
-    pub file: Option<String>,
+    pub file: Vec<String>,
 
     /// Filename for stdin file upload
     #[arg(short = 'n', long, default_value = "stdin")]
     pub filename: String,
 
+    /// Gzip files before upload (adds .gz); max_file_size then applies to the compressed size
+    #[arg(long, requires = "file")]
+    pub compress: bool,
+
+    /// Append each file's SHA-256 to the upload comment
+    #[arg(long, requires = "file")]
+    pub checksum: bool,
+
+    /// Send stdin text exactly as read, keeping trailing whitespace and newlines
+    #[arg(long)]
+    pub no_trim: bool,
+
+    /// How to treat invalid UTF-8 in stdin text: strict (refuse) or lossy (replace with U+FFFD)
+    #[arg(long, value_name = "MODE", default_value = "strict")]
+    pub encoding: Encoding,
+
+    /// Snippet type for uploaded files, for syntax highlighting (e.g. python, diff, shell)
+    #[arg(long, value_name = "LANG")]
+    pub snippet_type: Option<String>,
+
+    /// Display title for the uploaded file (defaults to the filename)
+    #[arg(long)]
+    pub title: Option<String>,
+
     /// Skip confirmation prompt
     #[arg(short = 'y', long)]
     pub yes: bool,
+
+    /// Reply in the thread of this message ts (overrides the profile's thread)
+    #[arg(long, value_name = "TS")]
+    pub thread_ts: Option<String>,
+
+    /// Skip sending if a message with this key was sent within dedupe_ttl (default 1h)
+    #[arg(long, value_name = "KEY")]
+    pub dedupe_key: Option<String>,
+
+    /// Block Kit blocks JSON file to send (`-` for stdin); -t becomes the fallback text
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["file", "me", "header", "sections", "fields", "context", "divider"]
+    )]
+    pub blocks: Option<String>,
+
+    /// Wrap the text in a code block (optional language hint, used for snippet uploads)
+    #[arg(
+        long,
+        value_name = "LANG",
+        num_args = 0..=1,
+        default_missing_value = "",
+        conflicts_with_all = ["file", "blocks"]
+    )]
+    pub code: Option<String>,
+
+    /// Render TSV/CSV input as an aligned table in a code block (delimiter auto-detected if omitted)
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        conflicts_with_all = ["file", "blocks", "code"]
+    )]
+    pub table: Option<Option<crate::table::Delimiter>>,
+
+    /// With --code/--table, upload over-long text as a snippet file instead of failing
+    #[arg(long)]
+    pub snippet_fallback: bool,
+
+    /// What to do with text over Slack's message limit (overrides the profile's long_text)
+    #[arg(long, value_name = "MODE", conflicts_with_all = ["file", "code", "table"])]
+    pub long_text: Option<LongText>,
+
+    /// Send the text literally: disable mrkdwn and escape <, >, & so nothing is reformatted
+    #[arg(
+        long,
+        conflicts_with_all = ["file", "me", "blocks", "header", "sections", "fields", "context", "divider", "code", "table", "color", "ephemeral"]
+    )]
+    pub raw: bool,
+
+    /// Don't show previews for links in the text (overrides the profile's unfurl_links)
+    #[arg(long)]
+    pub no_unfurl_links: bool,
+
+    /// Don't show previews for media links (overrides the profile's unfurl_media)
+    #[arg(long)]
+    pub no_unfurl_media: bool,
+
+    /// Ping the whole channel (asks for an extra confirmation unless -y)
+    #[arg(long, value_name = "WHO", conflicts_with_all = ["file", "me", "raw"])]
+    pub notify: Option<Notify>,
+
+    /// Prefix a mention of this user (user ID, email, or @handle; repeatable)
+    #[arg(long, value_name = "USER", conflicts_with = "raw")]
+    pub mention: Vec<String>,
+
+    /// Turn @handles in the text into real mentions (users.list, cached); overrides resolve_mentions
+    #[arg(long, conflicts_with = "raw")]
+    pub resolve_mentions: bool,
+
+    /// Severity: prefixes an emoji, colors the sidebar, and adds the profile's mention for that level
+    #[arg(long, conflicts_with = "raw")]
+    pub level: Option<Level>,
+
+    /// Sidebar color: good, warning, danger, or hex like #36a64f (sends the text as an attachment)
+    #[arg(
+        long,
+        conflicts_with_all = ["file", "me", "blocks", "header", "sections", "fields", "context", "divider"]
+    )]
+    pub color: Option<String>,
+
+    /// Header block text (builds a Block Kit message)
+    #[arg(long, visible_alias = "header-block")]
+    pub header: Option<String>,
+
+    /// Section block with mrkdwn text, placed after the message text (repeatable)
+    #[arg(long = "section", value_name = "TEXT")]
+    pub sections: Vec<String>,
+
+    /// Field block as KEY=VALUE (repeatable)
+    #[arg(long = "field", value_name = "KEY=VALUE")]
+    pub fields: Vec<String>,
+
+    /// Context block text shown in small print at the bottom
+    #[arg(long)]
+    pub context: Option<String>,
+
+    /// Insert a divider between the message text and fields
+    #[arg(long)]
+    pub divider: bool,
+
+    /// Show the message only to this user (user ID, email, or @handle)
+    #[arg(long, value_name = "USER", conflicts_with_all = ["file", "at", "output"])]
+    pub ephemeral: Option<String>,
+
+    /// Post as a /me action message (chat.meMessage; text only)
+    #[arg(
+        long,
+        conflicts_with_all = ["file", "at", "ephemeral", "thread_ts", "header", "sections", "fields", "context", "divider"]
+    )]
+    pub me: bool,
+
+    /// Schedule instead of sending now: RFC 3339 time or offset like +30m, +2h, +1d
+    #[arg(long, value_name = "TIME")]
+    pub at: Option<String>,
+
+    /// Mask token-like strings in the message and text uploads instead of refusing to send
+    #[arg(long)]
+    pub redact: bool,
+
+    /// Send even during the profile's quiet_hours
+    #[arg(long)]
+    pub force: bool,
+
+    /// Print the resolved destination and the exact request instead of sending
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Check the token, channel membership and file sizes without sending; exits non-zero on a problem
+    #[arg(long, conflicts_with = "dry_run")]
+    pub check: bool,
+
+    /// If Slack is unreachable (or min_interval hasn't passed), queue the message for `slafling flush`; overrides offline_queue
+    #[arg(long, conflicts_with_all = ["at", "ephemeral", "me"])]
+    pub queue: bool,
+
+    /// Output format for the sent message's channel/ts/permalink (json for scripts)
+    #[arg(short, long)]
+    pub output: Option<OutputFormat>,
+
+    /// Prefix the message with ✅/⚠️/❌ and "exited N" for a command's exit code
+    #[arg(long, value_name = "EXIT_CODE", allow_negative_numbers = true)]
+    pub status: Option<i32>,
+
+    /// Like --status, but read the exit code from SLAFLING_STATUS
+    #[arg(long, conflicts_with = "status")]
+    pub status_from_env: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Notify {
+    Here,
+    Channel,
+}
+
+impl Notify {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Here => "here",
+            Self::Channel => "channel",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum Encoding {
+    Strict,
+    Lossy,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum LongText {
+    /// Refuse to send
+    Error,
+    /// Drop the beginning, keeping the end (where a log's failure usually is)
+    TruncateHead,
+    /// Drop the end, keeping the beginning
+    TruncateTail,
+    /// Post the first chunk, then the rest as replies in its thread
+    Split,
+    /// Upload the text as message.txt with a one-line summary as the comment
+    Upload,
+}
+
+impl LongText {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::TruncateHead => "truncate-head",
+            Self::TruncateTail => "truncate-tail",
+            Self::Split => "split",
+            Self::Upload => "upload",
+        }
+    }
+}
+
+impl std::str::FromStr for LongText {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.to_lowercase().as_str() {
+            "error" => Ok(Self::Error),
+            "truncate-head" => Ok(Self::TruncateHead),
+            "truncate-tail" => Ok(Self::TruncateTail),
+            "split" => Ok(Self::Split),
+            "upload" => Ok(Self::Upload),
+            _ => bail!(
+                "invalid long_text '{}' (valid: error, truncate-head, truncate-tail, split, upload)",
+                s
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Level {
+    Info,
+    Warn,
+    Error,
+    Critical,
+}
+
+impl Level {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+            Self::Critical => "critical",
+        }
+    }
 }
 
 #[derive(Subcommand)]
 pub enum Command {
     /// Initialize config file
-    Init,
+    Init {
+        /// Generate config from SLAFLING_* environment variables
+        #[arg(long)]
+        from_env: bool,
+    },
 
     /// Validate config file
-    Validate,
+    Validate {
+        /// Also check each profile's token against Slack (required_scopes)
+        #[arg(long)]
+        remote: bool,
+
+        /// Fail on keys no setting reads (e.g. a typo like `chanel`)
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Send one message per JSONL line ({"text": "..."}) to the profile's channel
+    Batch {
+        /// JSONL file (reads from stdin if omitted)
+        file: Option<String>,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+
+        /// Send even during the profile's quiet_hours
+        #[arg(long)]
+        force: bool,
+    },
 
     /// Search for Slack channels by name
     Search {
@@ -67,6 +369,295 @@ pub enum Command {
         #[command(subcommand)]
         action: TokenAction,
     },
+
+    /// Inspect and remove reactions on a message in the profile's channel
+    Reactions {
+        #[command(subcommand)]
+        action: ReactionsAction,
+    },
+
+    /// Show which workspace, bot user, and app the resolved token belongs to
+    Whoami {
+        /// Output format (json for scripts; otherwise key: value lines)
+        #[arg(short, long)]
+        output: Option<OutputFormat>,
+    },
+
+    /// List the scopes the resolved token grants, and features unavailable without others
+    Scopes,
+
+    /// Post a marker message to the profile's channel and check it arrived (setup check)
+    Test {
+        /// Delete the marker message once it is verified
+        #[arg(long)]
+        delete: bool,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+
+        /// Send even during the profile's quiet_hours
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Look up a user's ID (and open DM channel) by email
+    Users {
+        /// Email address to look up
+        #[arg(long)]
+        email: String,
+
+        /// Output format (json for scripts; otherwise tab-separated id, name, DM channel)
+        #[arg(short, long)]
+        output: Option<OutputFormat>,
+    },
+
+    /// List user groups with their IDs (for `<!subteam^S…>` mentions)
+    Usergroups {
+        /// Output format (json for scripts; otherwise tab-separated id, @handle, name, mention)
+        #[arg(short, long)]
+        output: Option<OutputFormat>,
+    },
+
+    /// Repost a message (by permalink) to the profile's channel
+    Forward {
+        /// Message permalink (https://<workspace>.slack.com/archives/<channel>/p<ts>)
+        permalink: String,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+
+        /// Send even during the profile's quiet_hours
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Replace the text of a message the bot posted in the profile's channel
+    Edit {
+        /// Message timestamp (e.g. 1700000000.123456)
+        ts: String,
+
+        /// New message text
+        #[arg(short, long)]
+        text: String,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// Delete a message the bot posted in the profile's channel
+    Delete {
+        /// Message timestamp (e.g. 1700000000.123456)
+        ts: String,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// Delete the last message sent from this profile (from the local send history)
+    Undo {
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// Deliver messages queued while Slack was unreachable, oldest first
+    Flush,
+
+    /// Abort sends still held back by undo_delay (this profile's)
+    Cancel,
+
+    /// Inspect and manage messages queued while Slack was unreachable
+    Queue {
+        #[command(subcommand)]
+        action: QueueAction,
+    },
+
+    /// Replace the text of the last message sent from this profile (from the local send history)
+    EditLast {
+        /// New message text
+        #[arg(short, long)]
+        text: String,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// List or cancel scheduled messages in the profile's channel
+    Scheduled {
+        #[command(subcommand)]
+        action: ScheduledAction,
+    },
+
+    /// Manage profiles in the config file
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+
+    /// Maintain the config file against the workspace
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ReactionsAction {
+    /// List who reacted with what
+    List {
+        /// Message timestamp (e.g. 1700000000.123456)
+        ts: String,
+    },
+
+    /// Remove the bot's reaction from a message
+    Remove {
+        /// Message timestamp (e.g. 1700000000.123456)
+        ts: String,
+
+        /// Emoji name (e.g. thumbsup or :thumbsup:)
+        emoji: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum QueueAction {
+    /// List this profile's queued messages, oldest first
+    List {
+        /// Output format (auto-detected if omitted: table for TTY, tsv for pipe)
+        #[arg(short, long)]
+        output: Option<OutputFormat>,
+    },
+
+    /// Delete a queued message without sending it
+    Drop {
+        /// Queue entry ID (from `queue list`)
+        id: String,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// Try delivering now: one entry, or all of this profile's like `flush`
+    Retry {
+        /// Queue entry ID (from `queue list`)
+        id: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ScheduledAction {
+    /// List pending scheduled messages
+    List {
+        /// Output format (auto-detected if omitted: table for TTY, tsv for pipe)
+        #[arg(short, long)]
+        output: Option<OutputFormat>,
+    },
+
+    /// Cancel a scheduled message
+    Cancel {
+        /// Scheduled message ID (from `scheduled list` or `--at` output)
+        id: String,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProfileAction {
+    /// List profiles with their channel, confirmation and token
+    List {
+        /// Output format (auto-detected if omitted: table for TTY, tsv for pipe)
+        #[arg(short, long)]
+        output: Option<OutputFormat>,
+    },
+
+    /// Show a profile's settings merged over [default], as sends see them
+    Show {
+        /// Profile name ([default] if omitted)
+        name: Option<String>,
+
+        /// Output format (json for scripts; otherwise key: value lines)
+        #[arg(short, long)]
+        output: Option<OutputFormat>,
+    },
+
+    /// Add a new profile (prompts for a channel if --channel is omitted)
+    Add {
+        /// Profile name
+        name: String,
+
+        /// Target channel (e.g. "#general", "C01ABCDEF")
+        #[arg(short, long)]
+        channel: Option<String>,
+    },
+
+    /// Remove a profile
+    Remove {
+        /// Profile name
+        name: String,
+
+        /// Also delete the profile's stored token
+        #[arg(long)]
+        purge_token: bool,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// Rename a profile (moves its stored token too)
+    Rename {
+        /// Current profile name
+        from: String,
+
+        /// New profile name
+        to: String,
+    },
+
+    /// Copy a profile's settings to a new profile
+    Copy {
+        /// Source profile name
+        from: String,
+
+        /// New profile name
+        to: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print a value by dotted key (e.g. profiles.prod.channel)
+    Get {
+        /// Dotted key
+        key: String,
+    },
+
+    /// Set a value by dotted key, keeping comments (e.g. default.confirm true)
+    Set {
+        /// Dotted key
+        key: String,
+
+        /// TOML value (true, 30, ["a", "b"]); anything else is taken as a string
+        value: String,
+    },
+
+    /// Print a JSON Schema of the config file, for editor completion and validation
+    Schema,
+
+    /// Pin every profile's channel to its current ID and name (channel_id/channel_name)
+    Sync {
+        /// Show what would change without writing the config
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -117,6 +708,16 @@ pub enum OutputFormat {
     Json,
 }
 
+impl OutputFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Table => "table",
+            Self::Tsv => "tsv",
+            Self::Json => "json",
+        }
+    }
+}
+
 pub fn parse_channel_types_str(s: &str) -> anyhow::Result<Vec<ChannelType>> {
     s.split(',').map(|t| t.trim().parse()).collect()
 }
@@ -181,6 +782,152 @@ mod tests {
         assert_eq!(channel_types_to_api_string(&[]), "");
     }
 
+    #[test]
+    fn file_is_repeatable_and_may_read_stdin() {
+        let cli = Cli::try_parse_from(["slafling", "-f", "a.log", "-f", "b.log"]).unwrap();
+        assert_eq!(cli.send.file, vec!["a.log", "b.log"]);
+        let cli = Cli::try_parse_from(["slafling", "-f"]).unwrap();
+        assert_eq!(cli.send.file, vec![""]);
+        let cli = Cli::try_parse_from(["slafling", "-t", "hi"]).unwrap();
+        assert!(cli.send.file.is_empty());
+    }
+
+    #[test]
+    fn compress_requires_file() {
+        assert!(Cli::try_parse_from(["slafling", "-t", "hi", "--compress"]).is_err());
+        let cli = Cli::try_parse_from(["slafling", "-f", "a.log", "--compress"]).unwrap();
+        assert!(cli.send.compress);
+    }
+
+    #[test]
+    fn queue_conflicts_with_deferred_sends() {
+        assert!(Cli::try_parse_from(["slafling", "-t", "hi", "--queue", "--at", "+1h"]).is_err());
+        let cli = Cli::try_parse_from(["slafling", "-t", "hi", "--queue"]).unwrap();
+        assert!(cli.send.queue);
+        let cli = Cli::try_parse_from(["slafling", "flush"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Flush)));
+    }
+
+    #[test]
+    fn check_conflicts_with_dry_run() {
+        assert!(Cli::try_parse_from(["slafling", "--check", "--dry-run"]).is_err());
+        let cli = Cli::try_parse_from(["slafling", "--check", "-f", "report.html"]).unwrap();
+        assert!(cli.send.check);
+    }
+
+    #[test]
+    fn parse_profile_list() {
+        let cli = Cli::try_parse_from(["slafling", "profile", "list", "-o", "json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Profile {
+                action: ProfileAction::List {
+                    output: Some(OutputFormat::Json)
+                }
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_profile_show_defaults_to_default_profile() {
+        let cli = Cli::try_parse_from(["slafling", "profile", "show"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Profile {
+                action: ProfileAction::Show {
+                    name: None,
+                    output: None
+                }
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_profile_remove_yes() {
+        let cli = Cli::try_parse_from(["slafling", "profile", "remove", "staging", "-y"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Profile {
+                action: ProfileAction::Remove {
+                    purge_token: false,
+                    yes: true,
+                    ..
+                }
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_config_set() {
+        let cli =
+            Cli::try_parse_from(["slafling", "config", "set", "default.confirm", "true"]).unwrap();
+        match cli.command {
+            Some(Command::Config {
+                action: ConfigAction::Set { key, value },
+            }) => {
+                assert_eq!(key, "default.confirm");
+                assert_eq!(value, "true");
+            }
+            _ => panic!("expected config set"),
+        }
+    }
+
+    #[test]
+    fn parse_config_sync() {
+        let cli = Cli::try_parse_from(["slafling", "config", "sync", "--dry-run"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Config {
+                action: ConfigAction::Sync { dry_run: true }
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_cancel() {
+        let cli = Cli::try_parse_from(["slafling", "cancel", "-p", "deploy"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Cancel)));
+        assert_eq!(cli.profile.as_deref(), Some("deploy"));
+    }
+
+    #[test]
+    fn redirect_to_test_is_global() {
+        let cli = Cli::try_parse_from(["slafling", "batch", "--redirect-to-test"]).unwrap();
+        assert!(cli.redirect_to_test);
+        let cli = Cli::try_parse_from(["slafling", "--redirect-to-test", "-t", "hi"]).unwrap();
+        assert!(cli.redirect_to_test);
+    }
+
+    #[test]
+    fn config_is_global() {
+        let cli = Cli::try_parse_from(["slafling", "validate", "--config", "work.toml"]).unwrap();
+        assert_eq!(cli.config.as_deref(), Some("work.toml"));
+        let cli = Cli::try_parse_from(["slafling", "--config", "work.toml", "-t", "hi"]).unwrap();
+        assert_eq!(cli.config.as_deref(), Some("work.toml"));
+    }
+
+    #[test]
+    fn dest_is_global() {
+        let cli =
+            Cli::try_parse_from(["slafling", "-p", "svc", "--dest", "alerts", "-t", "hi"]).unwrap();
+        assert_eq!(cli.dest.as_deref(), Some("alerts"));
+        let cli = Cli::try_parse_from(["slafling", "batch", "--dest", "reports"]).unwrap();
+        assert_eq!(cli.dest.as_deref(), Some("reports"));
+    }
+
+    #[test]
+    fn parse_test() {
+        let cli = Cli::try_parse_from(["slafling", "test", "--delete", "-y"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Test {
+                delete: true,
+                yes: true,
+                force: false
+            })
+        ));
+    }
+
     #[test]
     fn channel_types_to_api_string_order_preserved() {
         assert_eq!(
@@ -0,0 +1,471 @@
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use toml_edit::{DocumentMut, Item, Key, Table, TableLike, Value};
+
+use super::file::{merge_includes, validate_config, ConfigFile};
+use crate::token::validate_profile_name;
+
+/// A config file loaded for in-place editing (comments and formatting preserved).
+pub struct ConfigDocument {
+    doc: DocumentMut,
+}
+
+impl ConfigDocument {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        Ok(Self {
+            doc: content.parse()?,
+        })
+    }
+
+    /// Validate the edited document and write it back.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = self.doc.to_string();
+        let mut config: ConfigFile =
+            toml::from_str(&content).context("edited config is not a valid config file")?;
+        merge_includes(&mut config, path)?;
+        validate_config(&config)?;
+        std::fs::write(path, content).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    fn profiles(&self) -> Option<&Table> {
+        self.doc.get("profiles").and_then(Item::as_table)
+    }
+
+    fn profiles_mut(&mut self) -> Result<&mut Table> {
+        if !self.doc.contains_key("profiles") {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            self.doc.insert("profiles", Item::Table(table));
+        }
+        self.doc["profiles"]
+            .as_table_mut()
+            .context("'profiles' in config is not a table")
+    }
+
+    pub fn has_profile(&self, name: &str) -> bool {
+        self.profiles().is_some_and(|p| p.contains_key(name))
+    }
+
+    fn ensure_exists(&self, name: &str) -> Result<()> {
+        if !self.has_profile(name) {
+            bail!("profile '{name}' not found in config");
+        }
+        Ok(())
+    }
+
+    fn ensure_absent(&self, name: &str) -> Result<()> {
+        validate_profile_name(name)?;
+        if name.starts_with('@') {
+            bail!("invalid profile name '{name}' (a leading @ selects a group)");
+        }
+        if self.has_profile(name) {
+            bail!("profile '{name}' already exists");
+        }
+        Ok(())
+    }
+
+    /// Insert a new profile table after everything else in the file, including trailing comments.
+    fn append_profile(&mut self, name: &str, mut table: Table) -> Result<()> {
+        let trailing = self.doc.trailing().as_str().unwrap_or_default().to_string();
+        if !trailing.trim().is_empty() {
+            table
+                .decor_mut()
+                .set_prefix(format!("{}\n\n", trailing.trim_end()));
+            self.doc.set_trailing("");
+        }
+        self.profiles_mut()?.insert(name, Item::Table(table));
+        Ok(())
+    }
+
+    pub fn add_profile(&mut self, name: &str, channel: &str) -> Result<()> {
+        self.ensure_absent(name)?;
+        let mut table = Table::new();
+        table.insert("channel", toml_edit::value(channel));
+        self.append_profile(name, table)
+    }
+
+    pub fn remove_profile(&mut self, name: &str) -> Result<()> {
+        self.ensure_exists(name)?;
+        self.profiles_mut()?.remove(name);
+        Ok(())
+    }
+
+    /// Rename a profile, keeping its position and comments in the file.
+    pub fn rename_profile(&mut self, from: &str, to: &str) -> Result<()> {
+        self.ensure_exists(from)?;
+        self.ensure_absent(to)?;
+        let profiles = self.profiles_mut()?;
+        let item = profiles.remove(from).context("profile disappeared")?;
+        profiles.insert(to, item);
+
+        // Keep [groups] pointing at the profile under its new name
+        let groups = self.doc.get_mut("groups").and_then(Item::as_table_like_mut);
+        for (_, members) in groups.into_iter().flat_map(|g| g.iter_mut()) {
+            for member in members
+                .as_array_mut()
+                .into_iter()
+                .flat_map(|a| a.iter_mut())
+            {
+                if member.as_str() == Some(from) {
+                    let decor = member.decor().clone();
+                    *member = to.into();
+                    *member.decor_mut() = decor;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Point a profile at conversation `id` pinned to `name`, replacing a `channel` entry with
+    /// `channel_id` + `channel_name`. Returns whether anything changed.
+    pub fn pin_channel(&mut self, profile: &str, id: &str, name: &str) -> Result<bool> {
+        self.ensure_exists(profile)?;
+        let table = self.profiles_mut()?[profile]
+            .as_table_mut()
+            .with_context(|| format!("profile '{profile}' is not a table"))?;
+        let current = |key: &str| table.get(key).and_then(Item::as_str).map(String::from);
+        if !table.contains_key("channel")
+            && current("channel_id").as_deref() == Some(id)
+            && current("channel_name").as_deref() == Some(name)
+        {
+            return Ok(false);
+        }
+        table.remove("channel");
+        set_value(table, "channel_id", id.into());
+        set_value(table, "channel_name", name.into());
+        Ok(true)
+    }
+
+    /// The value at a dotted key as `config get` prints it: strings bare, other values in
+    /// TOML syntax, tables as TOML under their full header.
+    pub fn get(&self, key: &str) -> Result<String> {
+        let parts = parse_key(key)?;
+        let mut item = self.doc.as_item();
+        for part in &parts {
+            item = item
+                .as_table_like()
+                .and_then(|t| t.get(part))
+                .with_context(|| format!("'{key}' is not set in config"))?;
+        }
+        let out = match item {
+            Item::Value(Value::String(s)) => s.value().clone(),
+            Item::Value(v) => v.clone().decorated("", "").to_string(),
+            Item::Table(t) => {
+                let mut table = t.clone();
+                table.decor_mut().clear();
+                for part in parts.iter().rev() {
+                    let mut outer = Table::new();
+                    outer.set_implicit(true);
+                    outer.insert(part, Item::Table(table));
+                    table = outer;
+                }
+                DocumentMut::from(table).to_string()
+            }
+            other => other.to_string(),
+        };
+        Ok(out.trim().to_string())
+    }
+
+    /// Set a dotted key, creating missing tables. `value` is read as a TOML value (`true`,
+    /// `30`, `["#a", "#b"]`) and otherwise taken as a plain string (`#deploy`, `1h`).
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let parts = parse_key(key)?;
+        let (last, tables) = parts.split_last().context("empty key")?;
+        if let [section, name, _, ..] = parts.as_slice() {
+            if section == "profiles" && !self.has_profile(name) {
+                validate_profile_name(name)?;
+            }
+        }
+        let value = value
+            .parse::<Value>()
+            .unwrap_or_else(|_| Value::from(value));
+
+        let mut table: &mut dyn TableLike = self.doc.as_table_mut();
+        for part in tables {
+            if !table.contains_key(part) {
+                let mut new = Table::new();
+                new.set_implicit(true);
+                table.insert(part, Item::Table(new));
+            }
+            table = table
+                .get_mut(part)
+                .and_then(Item::as_table_like_mut)
+                .with_context(|| format!("'{part}' in '{key}' is not a table"))?;
+        }
+        set_value(table, last, value);
+        Ok(())
+    }
+
+    /// Copy a profile's settings to a new profile appended at the end of the file.
+    pub fn copy_profile(&mut self, from: &str, to: &str) -> Result<()> {
+        self.ensure_exists(from)?;
+        self.ensure_absent(to)?;
+        let profiles = self.profiles_mut()?;
+        let source = profiles[from]
+            .as_table()
+            .with_context(|| format!("profile '{from}' is not a table"))?;
+        let mut table = Table::new();
+        for (key, value) in source.iter() {
+            table.insert(key, value.clone());
+        }
+        self.append_profile(to, table)
+    }
+}
+
+/// Split a dotted key like `profiles.prod.channel`; quoted parts (`profiles."a.b".channel`)
+/// may contain dots.
+fn parse_key(key: &str) -> Result<Vec<String>> {
+    let parts = Key::parse(key).map_err(|e| anyhow!("invalid key '{key}': {e}"))?;
+    Ok(parts.iter().map(|k| k.get().to_string()).collect())
+}
+
+/// Set a value, keeping an existing entry's inline comment.
+fn set_value(table: &mut dyn TableLike, key: &str, value: Value) {
+    match table.get_mut(key).and_then(Item::as_value_mut) {
+        Some(existing) => {
+            let decor = existing.decor().clone();
+            *existing = value;
+            *existing.decor_mut() = decor;
+        }
+        None => {
+            table.insert(key, Item::Value(value));
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.doc.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r##"# top comment
+[default]
+channel = "#general"
+
+# work profile
+[profiles.work]
+channel = "#work" # inline
+confirm = true
+
+[profiles.random]
+channel = "#random"
+"##;
+
+    fn sample() -> ConfigDocument {
+        ConfigDocument::parse(SAMPLE).unwrap()
+    }
+
+    fn parsed(doc: &ConfigDocument) -> ConfigFile {
+        toml::from_str(&doc.to_string()).unwrap()
+    }
+
+    #[test]
+    fn add_profile_appends_table() {
+        let mut doc = sample();
+        doc.add_profile("alerts", "#alerts").unwrap();
+        let cfg = parsed(&doc);
+        assert_eq!(cfg.profiles["alerts"].channel.as_deref(), Some("#alerts"));
+        assert!(doc.to_string().starts_with("# top comment"));
+        assert!(doc.to_string().contains("# work profile"));
+    }
+
+    #[test]
+    fn add_profile_without_profiles_section() {
+        let mut doc = ConfigDocument::parse("[default]\n").unwrap();
+        doc.add_profile("work", "#work").unwrap();
+        assert!(doc.to_string().contains("[profiles.work]"));
+        assert_eq!(
+            parsed(&doc).profiles["work"].channel.as_deref(),
+            Some("#work")
+        );
+    }
+
+    #[test]
+    fn add_profile_goes_after_trailing_comments() {
+        let mut doc =
+            ConfigDocument::parse("[default]\nchannel = \"#a\"\n# confirm = false\n").unwrap();
+        doc.add_profile("work", "#work").unwrap();
+        let out = doc.to_string();
+        let comment = out.find("# confirm = false").unwrap();
+        let header = out.find("[profiles.work]").unwrap();
+        assert!(comment < header, "unexpected layout:\n{out}");
+    }
+
+    #[test]
+    fn add_profile_rejects_duplicate() {
+        let mut doc = sample();
+        let err = doc.add_profile("work", "#x").unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+
+    #[test]
+    fn add_profile_rejects_invalid_name() {
+        let mut doc = sample();
+        assert!(doc.add_profile("../evil", "#x").is_err());
+    }
+
+    #[test]
+    fn remove_profile_drops_table() {
+        let mut doc = sample();
+        doc.remove_profile("random").unwrap();
+        let cfg = parsed(&doc);
+        assert!(!cfg.profiles.contains_key("random"));
+        assert!(cfg.profiles.contains_key("work"));
+    }
+
+    #[test]
+    fn remove_profile_missing() {
+        let mut doc = sample();
+        let err = doc.remove_profile("nope").unwrap_err();
+        assert!(err.to_string().contains("profile 'nope' not found"));
+    }
+
+    #[test]
+    fn rename_profile_preserves_comments() {
+        let mut doc = sample();
+        doc.rename_profile("work", "office").unwrap();
+        let out = doc.to_string();
+        assert!(out.contains("# work profile\n[profiles.office]"));
+        assert!(out.contains("# inline"));
+        let cfg = parsed(&doc);
+        assert!(!cfg.profiles.contains_key("work"));
+        assert_eq!(cfg.profiles["office"].confirm, Some(true));
+    }
+
+    #[test]
+    fn rename_profile_updates_groups() {
+        let mut doc = ConfigDocument::parse(
+            "[default]\n[profiles.work]\n[profiles.home]\n\
+             [groups]\nall = [\"work\", \"home\"] # both\n",
+        )
+        .unwrap();
+        doc.rename_profile("work", "office").unwrap();
+        assert!(doc
+            .to_string()
+            .contains("all = [\"office\", \"home\"] # both"));
+        let err = doc.rename_profile("home", "@home").unwrap_err();
+        assert!(err.to_string().contains("a leading @ selects a group"));
+    }
+
+    #[test]
+    fn rename_profile_rejects_existing_target() {
+        let mut doc = sample();
+        let err = doc.rename_profile("work", "random").unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+
+    #[test]
+    fn copy_profile_duplicates_settings() {
+        let mut doc = sample();
+        doc.copy_profile("work", "work2").unwrap();
+        let cfg = parsed(&doc);
+        assert_eq!(cfg.profiles["work2"].channel.as_deref(), Some("#work"));
+        assert_eq!(cfg.profiles["work2"].confirm, Some(true));
+        assert!(cfg.profiles.contains_key("work"));
+    }
+
+    #[test]
+    fn pin_channel_replaces_channel() {
+        let mut doc = sample();
+        assert!(doc.pin_channel("work", "C0123ABCD", "work").unwrap());
+        let cfg = parsed(&doc);
+        let work = &cfg.profiles["work"];
+        assert!(work.channel.is_none());
+        assert_eq!(work.channel_id.as_deref(), Some("C0123ABCD"));
+        assert_eq!(work.channel_name.as_deref(), Some("work"));
+        assert_eq!(work.confirm, Some(true));
+        assert!(!doc.pin_channel("work", "C0123ABCD", "work").unwrap());
+    }
+
+    #[test]
+    fn pin_channel_updates_name_keeping_comment() {
+        let mut doc = ConfigDocument::parse(
+            "[default]\n[profiles.ops]\nchannel_id = \"C1\"\nchannel_name = \"ops\" # pinned\n",
+        )
+        .unwrap();
+        assert!(doc.pin_channel("ops", "C1", "ops-renamed").unwrap());
+        assert!(doc
+            .to_string()
+            .contains("channel_name = \"ops-renamed\" # pinned"));
+    }
+
+    #[test]
+    fn get_prints_values_and_tables() {
+        let doc = sample();
+        assert_eq!(doc.get("profiles.work.channel").unwrap(), "#work");
+        assert_eq!(doc.get("profiles.work.confirm").unwrap(), "true");
+        assert_eq!(
+            doc.get("profiles.work").unwrap(),
+            "[profiles.work]\nchannel = \"#work\" # inline\nconfirm = true"
+        );
+        let err = doc.get("profiles.work.output").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("'profiles.work.output' is not set"));
+    }
+
+    #[test]
+    fn set_keeps_comments_and_types() {
+        let mut doc = sample();
+        doc.set("profiles.work.channel", "#office").unwrap();
+        doc.set("default.confirm", "true").unwrap();
+        doc.set("default.search_types", r#"["im", "mpim"]"#)
+            .unwrap();
+        let out = doc.to_string();
+        assert!(out.contains("channel = \"#office\" # inline"), "{out}");
+        assert!(out.starts_with("# top comment"));
+        let cfg = parsed(&doc);
+        assert_eq!(cfg.default.confirm, Some(true));
+        assert_eq!(
+            cfg.default.search_types.as_deref(),
+            Some(&["im".to_string(), "mpim".to_string()][..])
+        );
+    }
+
+    #[test]
+    fn set_creates_missing_tables() {
+        let mut doc = ConfigDocument::parse("[default]\n").unwrap();
+        doc.set("profiles.ops.channel", "#ops").unwrap();
+        doc.set("policy.denied_channels", r##"["#prod-*"]"##)
+            .unwrap();
+        assert!(doc.to_string().contains("[profiles.ops]"));
+        let cfg = parsed(&doc);
+        assert_eq!(cfg.profiles["ops"].channel.as_deref(), Some("#ops"));
+        assert!(doc.set(r#"profiles."../evil".channel"#, "#x").is_err());
+        assert!(doc.set("profiles.ops.channel.name", "#x").is_err());
+    }
+
+    #[test]
+    fn save_rejects_invalid_result() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let doc = ConfigDocument::parse("[default]\noutput = \"yaml\"\n").unwrap();
+        assert!(doc.save(&path).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn save_writes_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, SAMPLE).unwrap();
+        let mut doc = ConfigDocument::load(&path).unwrap();
+        doc.add_profile("new", "#new").unwrap();
+        doc.save(&path).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("[profiles.new]"));
+        assert!(content.contains("# top comment"));
+    }
+}
@@ -1,16 +1,23 @@
+use secrecy::SecretString;
+
 use super::util::is_truthy;
 
 /// All environment variables read at startup, in one place.
 #[derive(Debug, Default)]
 pub struct Env {
     pub headless: bool,
-    pub profile: Option<String>,       // normal mode only
-    pub token: Option<String>,         // headless only
-    pub channel: Option<String>,       // headless only
-    pub output: Option<String>,        // both modes
-    pub max_file_size: Option<String>, // both modes
-    pub confirm: Option<String>,       // both modes
-    pub search_types: Option<String>,  // both modes
+    pub profile: Option<String>,          // normal mode only
+    pub token: Option<SecretString>,      // headless only
+    pub channel: Option<String>,          // headless only
+    pub output: Option<String>,           // both modes
+    pub max_file_size: Option<String>,    // both modes
+    pub confirm: Option<String>,          // both modes
+    pub search_types: Option<String>,     // both modes
+    pub status: Option<String>,           // both modes (send --status-from-env)
+    pub history: Option<String>,          // both modes
+    pub api_base: Option<String>,         // both modes
+    pub redirect_channel: Option<String>, // both modes
+    pub config: Option<String>,           // both modes
 }
 
 impl Env {
@@ -23,18 +30,24 @@ impl Env {
                 .map(|v| is_truthy(&v))
                 .unwrap_or(false),
             profile: opt("SLAFLING_PROFILE"),
-            token: opt("SLAFLING_TOKEN"),
+            token: opt("SLAFLING_TOKEN").map(SecretString::from),
             channel: opt("SLAFLING_CHANNEL"),
             output: opt("SLAFLING_OUTPUT"),
             max_file_size: opt("SLAFLING_MAX_FILE_SIZE"),
             confirm: opt("SLAFLING_CONFIRM"),
             search_types: opt("SLAFLING_SEARCH_TYPES"),
+            status: opt("SLAFLING_STATUS"),
+            history: opt("SLAFLING_HISTORY"),
+            api_base: opt("SLAFLING_API_BASE"),
+            redirect_channel: opt("SLAFLING_REDIRECT_CHANNEL"),
+            config: opt("SLAFLING_CONFIG"),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use secrecy::ExposeSecret;
     use serial_test::serial;
 
     use super::*;
@@ -50,6 +63,11 @@ mod tests {
         assert!(env.max_file_size.is_none());
         assert!(env.confirm.is_none());
         assert!(env.search_types.is_none());
+        assert!(env.status.is_none());
+        assert!(env.history.is_none());
+        assert!(env.api_base.is_none());
+        assert!(env.redirect_channel.is_none());
+        assert!(env.config.is_none());
     }
 
     #[test]
@@ -64,6 +82,11 @@ mod tests {
             ("SLAFLING_MAX_FILE_SIZE", "50MB"),
             ("SLAFLING_CONFIRM", "true"),
             ("SLAFLING_SEARCH_TYPES", "im,mpim"),
+            ("SLAFLING_STATUS", "3"),
+            ("SLAFLING_HISTORY", "1"),
+            ("SLAFLING_API_BASE", "http://localhost:8080/api"),
+            ("SLAFLING_REDIRECT_CHANNEL", "#slafling-test"),
+            ("SLAFLING_CONFIG", "/tmp/slafling-ci.toml"),
         ];
         let prev: Vec<_> = keys
             .iter()
@@ -84,12 +107,20 @@ mod tests {
 
         assert!(env.headless);
         assert_eq!(env.profile.as_deref(), Some("work"));
-        assert_eq!(env.token.as_deref(), Some("xoxb-test"));
+        assert_eq!(
+            env.token.as_ref().map(|t| t.expose_secret()),
+            Some("xoxb-test")
+        );
         assert_eq!(env.channel.as_deref(), Some("#general"));
         assert_eq!(env.output.as_deref(), Some("json"));
         assert_eq!(env.max_file_size.as_deref(), Some("50MB"));
         assert_eq!(env.confirm.as_deref(), Some("true"));
         assert_eq!(env.search_types.as_deref(), Some("im,mpim"));
+        assert_eq!(env.status.as_deref(), Some("3"));
+        assert_eq!(env.history.as_deref(), Some("1"));
+        assert_eq!(env.api_base.as_deref(), Some("http://localhost:8080/api"));
+        assert_eq!(env.redirect_channel.as_deref(), Some("#slafling-test"));
+        assert_eq!(env.config.as_deref(), Some("/tmp/slafling-ci.toml"));
     }
 
     #[test]
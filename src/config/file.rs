@@ -1,10 +1,18 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::OnceLock;
 
 use anyhow::{anyhow, bail, Context, Result};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 
+use crate::quiet_hours::QuietHours;
+
+use super::env::Env;
+use super::policy::{ChannelPolicy, ContentRules, PolicyConfig};
+use super::util::{is_truthy, parse_duration, parse_file_size};
+
 // ── TokenStore enum ──────────────────────────────────────────────────────────
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -42,39 +50,314 @@ impl FromStr for TokenStore {
     }
 }
 
+// ── DndCheck enum ────────────────────────────────────────────────────────────
+
+/// What to do when a DM recipient is in do-not-disturb.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DndCheck {
+    #[default]
+    Off,
+    Warn,
+    Confirm,
+}
+
+impl FromStr for DndCheck {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "warn" => Ok(Self::Warn),
+            "confirm" => Ok(Self::Confirm),
+            _ => bail!("invalid dnd_check '{}' (valid: off, warn, confirm)", s),
+        }
+    }
+}
+
+// ── SharedCheck enum ─────────────────────────────────────────────────────────
+
+/// What to do when the destination is shared with another organization (Slack Connect).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SharedCheck {
+    #[default]
+    Off,
+    Warn,
+    Confirm,
+    Deny,
+}
+
+impl FromStr for SharedCheck {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "warn" => Ok(Self::Warn),
+            "confirm" => Ok(Self::Confirm),
+            "deny" => Ok(Self::Deny),
+            _ => bail!(
+                "invalid shared_check '{}' (valid: off, warn, confirm, deny)",
+                s
+            ),
+        }
+    }
+}
+
+// ── SecretScan enum ──────────────────────────────────────────────────────────
+
+/// What to do when outgoing text or a text upload contains something credential-shaped.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SecretScan {
+    Off,
+    #[default]
+    Block,
+    Redact,
+}
+
+impl FromStr for SecretScan {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "block" => Ok(Self::Block),
+            "redact" => Ok(Self::Redact),
+            _ => bail!("invalid secret_scan '{}' (valid: off, block, redact)", s),
+        }
+    }
+}
+
+// ── QuietHoursAction enum ───────────────────────────────────────────────────
+
+/// What happens to a send during `quiet_hours`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum QuietHoursAction {
+    #[default]
+    Block,
+    /// Schedule it for the end of the window instead
+    Defer,
+}
+
+impl FromStr for QuietHoursAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "block" => Ok(Self::Block),
+            "defer" => Ok(Self::Defer),
+            _ => bail!("invalid quiet_hours_action '{}' (valid: block, defer)", s),
+        }
+    }
+}
+
+// ── LabelColor enum ──────────────────────────────────────────────────────────
+
+/// Background color for a profile's `label` in prompts on a color terminal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LabelColor {
+    Red,
+    Yellow,
+    Green,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl FromStr for LabelColor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "red" => Ok(Self::Red),
+            "yellow" => Ok(Self::Yellow),
+            "green" => Ok(Self::Green),
+            "blue" => Ok(Self::Blue),
+            "magenta" => Ok(Self::Magenta),
+            "cyan" => Ok(Self::Cyan),
+            _ => bail!(
+                "invalid label_color '{}' (valid: red, yellow, green, blue, magenta, cyan)",
+                s
+            ),
+        }
+    }
+}
+
 // ── TOML types ───────────────────────────────────────────────────────────────
 
 #[derive(Deserialize)]
 pub struct ConfigFile {
+    /// Glob patterns of files whose `[profiles.*]` are merged in by `merge_includes`
+    #[serde(default)]
+    pub include: Vec<String>,
     pub default: DefaultConfig,
     #[serde(default)]
     pub profiles: HashMap<String, Profile>,
+    /// `[groups]`: named lists of profiles a send goes through in turn with `-p @name`
+    #[serde(default)]
+    pub groups: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub policy: PolicyConfig,
 }
 
-#[derive(Deserialize)]
+#[derive(Default, Deserialize)]
 pub struct DefaultConfig {
     pub channel: Option<String>,
     pub max_file_size: Option<String>,
     pub confirm: Option<bool>,
+    pub confirm_timeout: Option<String>,
+    pub protected: Option<bool>,
+    pub label: Option<String>,
+    pub label_color: Option<String>,
     pub output: Option<String>,
     pub search_types: Option<Vec<String>>,
     pub token_store: Option<String>,
+    pub fallback_token_profile: Option<String>,
+    pub required_scopes: Option<Vec<String>>,
+    pub deny_patterns: Option<Vec<String>>,
+    pub require_patterns: Option<Vec<String>>,
+    pub history: Option<bool>,
+    pub unfurl_links: Option<bool>,
+    pub unfurl_media: Option<bool>,
+    pub link_names: Option<bool>,
+    pub resolve_mentions: Option<bool>,
+    pub offline_queue: Option<bool>,
+    pub auto_join: Option<bool>,
+    pub fallback_channel: Option<String>,
+    pub test_channel: Option<String>,
+    pub dedupe_ttl: Option<String>,
+    pub undo_delay: Option<String>,
+    pub min_interval: Option<String>,
+    pub quiet_hours: Option<String>,
+    pub quiet_hours_action: Option<String>,
+    pub strip_exif: Option<bool>,
+    pub trim_stdin: Option<bool>,
+    pub dnd_check: Option<String>,
+    pub shared_check: Option<String>,
+    pub secret_scan: Option<String>,
+    pub long_text: Option<String>,
+    pub retries: Option<u32>,
+    pub retry_delay: Option<String>,
+    pub timeout: Option<String>,
+    pub proxy: Option<String>,
+    pub ca_bundle: Option<String>,
+    pub api_base: Option<String>,
+    pub levels: Option<HashMap<String, LevelTemplate>>,
 }
 
-#[derive(Deserialize)]
+#[derive(Default, Deserialize)]
 pub struct Profile {
     pub channel: Option<String>,
     pub max_file_size: Option<String>,
     pub confirm: Option<bool>,
+    pub confirm_timeout: Option<String>,
+    pub protected: Option<bool>,
+    pub label: Option<String>,
+    pub label_color: Option<String>,
     pub output: Option<String>,
     pub search_types: Option<Vec<String>>,
+    pub fallback_token_profile: Option<String>,
+    pub required_scopes: Option<Vec<String>>,
+    pub deny_patterns: Option<Vec<String>>,
+    pub require_patterns: Option<Vec<String>>,
+    pub thread: Option<String>,
+    /// Group DM members (user IDs), used instead of `channel`
+    pub users: Option<Vec<String>>,
+    /// Conversation ID used instead of `channel`, optionally pinned to `channel_name`
+    pub channel_id: Option<String>,
+    /// Name `channel_id` must still have at send time (renamed/repurposed channels are refused)
+    pub channel_name: Option<String>,
+    /// Named channels picked per send with `--dest`, used instead of `channel`
+    pub destinations: Option<HashMap<String, String>>,
+    /// Channels every send is broadcast to, used instead of `channel`
+    pub channels: Option<Vec<String>>,
+    /// Refuse `-y`, non-TTY use and headless mode for this profile
+    pub require_interactive: Option<bool>,
+    pub history: Option<bool>,
+    pub unfurl_links: Option<bool>,
+    pub unfurl_media: Option<bool>,
+    pub link_names: Option<bool>,
+    pub resolve_mentions: Option<bool>,
+    pub offline_queue: Option<bool>,
+    pub auto_join: Option<bool>,
+    pub fallback_channel: Option<String>,
+    pub test_channel: Option<String>,
+    pub dedupe_ttl: Option<String>,
+    pub undo_delay: Option<String>,
+    pub min_interval: Option<String>,
+    pub quiet_hours: Option<String>,
+    pub quiet_hours_action: Option<String>,
+    pub strip_exif: Option<bool>,
+    pub trim_stdin: Option<bool>,
+    pub dnd_check: Option<String>,
+    pub shared_check: Option<String>,
+    pub secret_scan: Option<String>,
+    pub long_text: Option<String>,
+    pub retries: Option<u32>,
+    pub retry_delay: Option<String>,
+    pub timeout: Option<String>,
+    pub proxy: Option<String>,
+    pub ca_bundle: Option<String>,
+    pub api_base: Option<String>,
+    pub levels: Option<HashMap<String, LevelTemplate>>,
+}
+
+/// Per-level formatting for `--level` (`[default.levels.<level>]`, `[profiles.<name>.levels.<level>]`).
+/// Unset fields fall back to the next layer; an empty string turns the built-in off.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct LevelTemplate {
+    pub emoji: Option<String>,
+    pub color: Option<String>,
+    pub mention: Option<String>,
+}
+
+impl LevelTemplate {
+    /// Layer `other` on top of `self`, field by field.
+    pub fn merge(&mut self, other: &LevelTemplate) {
+        if other.emoji.is_some() {
+            self.emoji = other.emoji.clone();
+        }
+        if other.color.is_some() {
+            self.color = other.color.clone();
+        }
+        if other.mention.is_some() {
+            self.mention = other.mention.clone();
+        }
+    }
 }
 
 // ── Config file I/O ──────────────────────────────────────────────────────────
 
+static CONFIG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `path` instead of the default config location (`--config` / `SLAFLING_CONFIG`);
+/// only the first call takes effect.
+pub fn set_config_path(path: PathBuf) {
+    let _ = CONFIG_PATH.set(path);
+}
+
 pub fn config_path() -> Result<PathBuf> {
+    if let Some(path) = CONFIG_PATH.get() {
+        return Ok(path.clone());
+    }
     let home = dirs::home_dir().context("could not determine home directory")?;
-    Ok(home.join(".config").join("slafling").join("config.toml"))
+    // `dirs::config_dir` only honors XDG_CONFIG_HOME on Linux
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(dirs::config_dir);
+    Ok(pick_config_path(
+        config_dir.as_deref(),
+        &home.join(".config"),
+    ))
+}
+
+/// `<config_dir>/slafling/config.toml`, unless only the pre-XDG `~/.config` file exists.
+fn pick_config_path(config_dir: Option<&Path>, legacy_dir: &Path) -> PathBuf {
+    let legacy = legacy_dir.join("slafling").join("config.toml");
+    match config_dir.map(|dir| dir.join("slafling").join("config.toml")) {
+        Some(path) if path.exists() || !legacy.exists() => path,
+        _ => legacy,
+    }
 }
 
 pub fn generate_init_config() -> String {
@@ -87,30 +370,173 @@ pub fn generate_init_config() -> String {
     )
 }
 
-pub fn write_init_config(path: &std::path::Path) -> Result<()> {
+/// Generate a config whose `[default]` section mirrors the `SLAFLING_*` env vars used in headless mode.
+pub fn generate_config_from_env(env: &Env) -> Result<String> {
+    let mut content = generate_init_config();
+    let mut set = |needle: &str, value: String| {
+        let key = needle.split(" = ").next().unwrap_or(needle);
+        content = content.replace(&format!("# {needle}"), &format!("{key} = {value}"));
+    };
+
+    if let Some(val) = &env.channel {
+        set(
+            "channel = \"#general\"",
+            toml::Value::from(val.as_str()).to_string(),
+        );
+    }
+    if let Some(val) = &env.max_file_size {
+        parse_file_size(val).map_err(|e| anyhow!("SLAFLING_MAX_FILE_SIZE: {}", e))?;
+        set(
+            "max_file_size = \"100MB\"",
+            toml::Value::from(val.as_str()).to_string(),
+        );
+    }
+    if let Some(val) = &env.confirm {
+        set("confirm = false", is_truthy(val).to_string());
+    }
+    if let Some(val) = &env.output {
+        let format: crate::cli::OutputFormat =
+            val.parse().map_err(|e| anyhow!("SLAFLING_OUTPUT: {}", e))?;
+        set(
+            "output = \"table\"",
+            toml::Value::from(format.as_str()).to_string(),
+        );
+    }
+    if let Some(val) = &env.search_types {
+        let types = crate::cli::parse_channel_types_str(val)
+            .map_err(|e| anyhow!("SLAFLING_SEARCH_TYPES: {}", e))?;
+        let array: toml::value::Array = types.iter().map(|t| t.as_api_str().into()).collect();
+        set(
+            "search_types = [\"public_channel\", \"private_channel\"]",
+            toml::Value::Array(array).to_string(),
+        );
+    }
+
+    Ok(content)
+}
+
+pub fn write_config(path: &Path, content: &str) -> Result<()> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)
             .with_context(|| format!("failed to create directory {}", parent.display()))?;
     }
-    let content = generate_init_config();
-    std::fs::write(path, &content)
-        .with_context(|| format!("failed to write {}", path.display()))?;
+    std::fs::write(path, content).with_context(|| format!("failed to write {}", path.display()))?;
     Ok(())
 }
 
+/// Copy an existing config to a timestamped sibling (`config.toml.bak-<YYYYMMDDTHHMMSS>`).
+pub fn backup_config(path: &Path) -> Result<PathBuf> {
+    let stamp = chrono::Local::now().format("%Y%m%dT%H%M%S");
+    let mut name = path
+        .file_name()
+        .context("invalid config path")?
+        .to_os_string();
+    name.push(format!(".bak-{stamp}"));
+    let backup = path.with_file_name(name);
+    std::fs::copy(path, &backup).with_context(|| {
+        format!(
+            "failed to back up {} to {}",
+            path.display(),
+            backup.display()
+        )
+    })?;
+    Ok(backup)
+}
+
 pub fn load_config() -> Result<ConfigFile> {
-    let path = config_path()?;
-    let content = std::fs::read_to_string(&path)
-        .with_context(|| format!("failed to read {}", path.display()))?;
-    let config: ConfigFile =
-        toml::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))?;
+    load_config_at(&config_path()?)
+}
+
+fn load_config_at(path: &Path) -> Result<ConfigFile> {
+    let mut config: ConfigFile = read_toml(path)?;
+    merge_includes(&mut config, path)?;
     validate_config(&config)?;
     Ok(config)
 }
 
+fn read_toml<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    toml::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+/// The config file's `[policy]` for headless mode, which otherwise ignores the file, so
+/// host-wide limits hold whenever the file exists. A file that can't be read is an error.
+pub fn load_host_policy() -> Result<ChannelPolicy> {
+    #[derive(Deserialize)]
+    struct PolicyOnly {
+        #[serde(default)]
+        policy: PolicyConfig,
+    }
+
+    let Ok(path) = config_path() else {
+        return Ok(ChannelPolicy::default());
+    };
+    if !path.exists() {
+        return Ok(ChannelPolicy::default());
+    }
+    let file: PolicyOnly = read_toml(&path)?;
+    ChannelPolicy::new(&file.policy)
+}
+
+/// An `include`d file: profiles only, so drop-ins can't change `[default]` or `[policy]`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IncludedFile {
+    #[serde(default)]
+    profiles: HashMap<String, Profile>,
+}
+
+/// Merge `[profiles.*]` from the files matched by `include`. Patterns are relative to the
+/// config file's directory (`~/` = home) and each one's matches are read in sorted order;
+/// a later file replaces an earlier file's profile of the same name, and profiles in the
+/// config file itself win over all of them.
+pub(super) fn merge_includes(config: &mut ConfigFile, path: &Path) -> Result<()> {
+    let base = path.parent().unwrap_or(Path::new("."));
+    let mut included = HashMap::new();
+    for pattern in &config.include {
+        for file in include_paths(base, pattern)? {
+            if file == path {
+                continue;
+            }
+            let file_config: IncludedFile = read_toml(&file)?;
+            included.extend(file_config.profiles);
+        }
+    }
+    for (name, profile) in included {
+        config.profiles.entry(name).or_insert(profile);
+    }
+    Ok(())
+}
+
+/// Files matching one `include` entry. A glob may match nothing; a plain path must exist.
+pub(super) fn include_paths(base: &Path, pattern: &str) -> Result<Vec<PathBuf>> {
+    let full = match pattern.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .context("could not determine home directory")?
+            .join(rest),
+        None => base.join(pattern),
+    };
+    if !pattern.contains(['*', '?', '[']) {
+        if !full.is_file() {
+            bail!("included file {} not found", full.display());
+        }
+        return Ok(vec![full]);
+    }
+    let mut paths: Vec<PathBuf> = glob::glob(&full.to_string_lossy())
+        .with_context(|| format!("invalid include pattern '{pattern}'"))?
+        .filter_map(|entry| entry.ok())
+        .filter(|p| p.is_file())
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
 // ── Validation ───────────────────────────────────────────────────────────────
 
 pub(super) fn validate_config(config: &ConfigFile) -> Result<()> {
+    ChannelPolicy::new(&config.policy)?;
+
     validate_section_values(
         "default",
         config.default.output.as_deref(),
@@ -126,17 +552,362 @@ pub(super) fn validate_config(config: &ConfigFile) -> Result<()> {
         }
     }
 
+    validate_fallback_profile(
+        config,
+        "default",
+        config.default.fallback_token_profile.as_deref(),
+    )?;
+    validate_levels("default", config.default.levels.as_ref())?;
+    validate_label(
+        "default",
+        config.default.label.as_deref(),
+        config.default.label_color.as_deref(),
+    )?;
+    if let Some(val) = &config.default.dnd_check {
+        val.parse::<DndCheck>()
+            .map_err(|e| anyhow!("{} in [default]", e))?;
+    }
+    if let Some(val) = &config.default.shared_check {
+        val.parse::<SharedCheck>()
+            .map_err(|e| anyhow!("{} in [default]", e))?;
+    }
+    validate_content_rules(
+        "default",
+        config.default.deny_patterns.as_deref(),
+        config.default.require_patterns.as_deref(),
+    )?;
+    if let Some(val) = &config.default.secret_scan {
+        val.parse::<SecretScan>()
+            .map_err(|e| anyhow!("{} in [default]", e))?;
+    }
+    if let Some(val) = &config.default.long_text {
+        val.parse::<crate::cli::LongText>()
+            .map_err(|e| anyhow!("{} in [default]", e))?;
+    }
+    if let Some(val) = &config.default.retry_delay {
+        parse_duration(val).map_err(|e| anyhow!("invalid retry_delay in [default]: {e}"))?;
+    }
+    if let Some(val) = &config.default.confirm_timeout {
+        parse_duration(val).map_err(|e| anyhow!("invalid confirm_timeout in [default]: {e}"))?;
+    }
+    if let Some(val) = &config.default.undo_delay {
+        parse_duration(val).map_err(|e| anyhow!("invalid undo_delay in [default]: {e}"))?;
+    }
+    if let Some(val) = &config.default.min_interval {
+        parse_duration(val).map_err(|e| anyhow!("invalid min_interval in [default]: {e}"))?;
+    }
+    if let Some(val) = &config.default.dedupe_ttl {
+        parse_duration(val).map_err(|e| anyhow!("invalid dedupe_ttl in [default]: {e}"))?;
+    }
+    if let Some(val) = &config.default.quiet_hours {
+        val.parse::<QuietHours>()
+            .map_err(|e| anyhow!("{} in [default]", e))?;
+    }
+    if let Some(val) = &config.default.quiet_hours_action {
+        val.parse::<QuietHoursAction>()
+            .map_err(|e| anyhow!("{} in [default]", e))?;
+    }
+    if let Some(val) = &config.default.timeout {
+        parse_duration(val).map_err(|e| anyhow!("invalid timeout in [default]: {e}"))?;
+    }
+    if let Some(val) = &config.default.proxy {
+        crate::slack::parse_proxy(val).map_err(|e| anyhow!("{e} in [default]"))?;
+    }
+    if let Some(val) = &config.default.api_base {
+        crate::slack::parse_api_base(val).map_err(|e| anyhow!("{e} in [default]"))?;
+    }
+
     for (name, profile) in &config.profiles {
+        let section = format!("profiles.{name}");
         validate_section_values(
-            &format!("profiles.{name}"),
+            &section,
             profile.output.as_deref(),
             profile.search_types.as_deref(),
         )?;
+        validate_fallback_profile(config, &section, profile.fallback_token_profile.as_deref())?;
+        validate_levels(&section, profile.levels.as_ref())?;
+        validate_label(
+            &section,
+            profile.label.as_deref(),
+            profile.label_color.as_deref(),
+        )?;
+        if let Some(val) = &profile.dnd_check {
+            val.parse::<DndCheck>()
+                .map_err(|e| anyhow!("{} in [{section}]", e))?;
+        }
+        if let Some(val) = &profile.shared_check {
+            val.parse::<SharedCheck>()
+                .map_err(|e| anyhow!("{} in [{section}]", e))?;
+        }
+        validate_content_rules(
+            &section,
+            profile.deny_patterns.as_deref(),
+            profile.require_patterns.as_deref(),
+        )?;
+        if let Some(val) = &profile.secret_scan {
+            val.parse::<SecretScan>()
+                .map_err(|e| anyhow!("{} in [{section}]", e))?;
+        }
+        if let Some(val) = &profile.long_text {
+            val.parse::<crate::cli::LongText>()
+                .map_err(|e| anyhow!("{} in [{section}]", e))?;
+        }
+        if let Some(val) = &profile.retry_delay {
+            parse_duration(val).map_err(|e| anyhow!("invalid retry_delay in [{section}]: {e}"))?;
+        }
+        if let Some(val) = &profile.confirm_timeout {
+            parse_duration(val)
+                .map_err(|e| anyhow!("invalid confirm_timeout in [{section}]: {e}"))?;
+        }
+        if let Some(val) = &profile.undo_delay {
+            parse_duration(val).map_err(|e| anyhow!("invalid undo_delay in [{section}]: {e}"))?;
+        }
+        if let Some(val) = &profile.min_interval {
+            parse_duration(val).map_err(|e| anyhow!("invalid min_interval in [{section}]: {e}"))?;
+        }
+        if let Some(val) = &profile.dedupe_ttl {
+            parse_duration(val).map_err(|e| anyhow!("invalid dedupe_ttl in [{section}]: {e}"))?;
+        }
+        if let Some(val) = &profile.quiet_hours {
+            val.parse::<QuietHours>()
+                .map_err(|e| anyhow!("{} in [{section}]", e))?;
+        }
+        if let Some(val) = &profile.quiet_hours_action {
+            val.parse::<QuietHoursAction>()
+                .map_err(|e| anyhow!("{} in [{section}]", e))?;
+        }
+        if let Some(val) = &profile.timeout {
+            parse_duration(val).map_err(|e| anyhow!("invalid timeout in [{section}]: {e}"))?;
+        }
+        if let Some(val) = &profile.proxy {
+            crate::slack::parse_proxy(val).map_err(|e| anyhow!("{e} in [{section}]"))?;
+        }
+        if let Some(val) = &profile.api_base {
+            crate::slack::parse_api_base(val).map_err(|e| anyhow!("{e} in [{section}]"))?;
+        }
+        if let Some(users) = &profile.users {
+            validate_group_dm(
+                &section,
+                profile.channel.is_some() || profile.channel_id.is_some(),
+                users,
+            )?;
+        }
+        validate_channel_pin(
+            &section,
+            profile.channel.is_some(),
+            profile.channel_id.as_deref(),
+            profile.channel_name.as_deref(),
+        )?;
+        if let Some(destinations) = &profile.destinations {
+            validate_destinations(
+                &section,
+                profile.channel.is_some()
+                    || profile.channel_id.is_some()
+                    || profile.users.is_some(),
+                destinations,
+            )?;
+        }
+        if let Some(channels) = &profile.channels {
+            validate_broadcast(
+                &section,
+                profile.channel.is_some()
+                    || profile.channel_id.is_some()
+                    || profile.users.is_some()
+                    || profile.destinations.is_some(),
+                channels,
+            )?;
+        }
+        if let Some(ts) = &profile.thread {
+            if !is_message_ts(ts) {
+                bail!("invalid thread in [{section}]: '{ts}' (expected a message ts like 1712345678.123456)");
+            }
+        }
     }
 
+    validate_groups(config)
+}
+
+fn validate_groups(config: &ConfigFile) -> Result<()> {
+    let mut names: Vec<&String> = config.groups.keys().collect();
+    names.sort();
+    for name in names {
+        let members = &config.groups[name];
+        if members.is_empty() {
+            bail!("group '{name}' in [groups] is empty");
+        }
+        for (i, member) in members.iter().enumerate() {
+            if !config.profiles.contains_key(member) {
+                bail!(
+                    "group '{name}' in [groups] lists '{member}', which is not a defined profile"
+                );
+            }
+            if members[..i].contains(member) {
+                bail!("group '{name}' in [groups] lists '{member}' twice");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The profiles of `[groups] name`, in the order a group send goes through them.
+pub fn group_members<'a>(config: &'a ConfigFile, name: &str) -> Result<&'a [String]> {
+    match config.groups.get(name) {
+        Some(members) => Ok(members),
+        None => bail!("group '{name}' not found in config"),
+    }
+}
+
+fn validate_fallback_profile(
+    config: &ConfigFile,
+    section: &str,
+    fallback: Option<&str>,
+) -> Result<()> {
+    if let Some(name) = fallback {
+        if !config.profiles.contains_key(name) {
+            bail!("fallback_token_profile '{name}' in [{section}] is not a defined profile");
+        }
+    }
+    Ok(())
+}
+
+/// Most members conversations.open accepts besides the bot itself.
+const MAX_GROUP_DM_USERS: usize = 8;
+
+fn validate_group_dm(section: &str, has_channel: bool, users: &[String]) -> Result<()> {
+    if has_channel {
+        bail!("[{section}] sets both channel and users; use one destination");
+    }
+    if users.is_empty() || users.len() > MAX_GROUP_DM_USERS {
+        bail!("users in [{section}] must list 1 to {MAX_GROUP_DM_USERS} user IDs");
+    }
+    if let Some(bad) = users.iter().find(|u| !crate::slack::is_user_id(u)) {
+        bail!("invalid user '{bad}' in [{section}] (expected a user ID like U012AB3CD)");
+    }
+    Ok(())
+}
+
+fn validate_destinations(
+    section: &str,
+    has_channel: bool,
+    destinations: &HashMap<String, String>,
+) -> Result<()> {
+    if has_channel {
+        bail!("[{section}] sets destinations along with channel, channel_id or users; use one");
+    }
+    if destinations.is_empty() {
+        bail!("destinations in [{section}] is empty");
+    }
+    let mut names: Vec<&String> = destinations.keys().collect();
+    names.sort();
+    for name in names {
+        if name.is_empty() || destinations[name].trim_start_matches('#').is_empty() {
+            bail!("destinations in [{section}] has an empty name or channel ('{name}')");
+        }
+    }
+    Ok(())
+}
+
+fn validate_broadcast(section: &str, has_channel: bool, channels: &[String]) -> Result<()> {
+    if has_channel {
+        bail!("[{section}] sets channels along with channel, channel_id, users or destinations; use one");
+    }
+    if channels.is_empty() {
+        bail!("channels in [{section}] is empty");
+    }
+    for (i, channel) in channels.iter().enumerate() {
+        if channel.trim_start_matches('#').is_empty() {
+            bail!("channels in [{section}] has an empty channel");
+        }
+        if channels[..i].contains(channel) {
+            bail!("channels in [{section}] lists '{channel}' twice");
+        }
+    }
+    Ok(())
+}
+
+fn validate_channel_pin(
+    section: &str,
+    has_channel: bool,
+    id: Option<&str>,
+    name: Option<&str>,
+) -> Result<()> {
+    match (id, name) {
+        (Some(_), _) if has_channel => {
+            bail!("[{section}] sets both channel and channel_id; use one destination")
+        }
+        (Some(id), _) if !crate::slack::is_conversation_id(id) => bail!(
+            "invalid channel_id '{id}' in [{section}] (expected a conversation ID like C0123ABCD)"
+        ),
+        (None, Some(_)) => bail!("channel_name in [{section}] requires channel_id"),
+        (_, Some(name)) if name.trim_start_matches('#').is_empty() => {
+            bail!("channel_name in [{section}] is empty")
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_levels(section: &str, levels: Option<&HashMap<String, LevelTemplate>>) -> Result<()> {
+    for (level, template) in levels.into_iter().flatten() {
+        if <crate::cli::Level as clap::ValueEnum>::from_str(level, false).is_err() {
+            bail!("invalid level '{level}' in [{section}.levels] (valid: info, warn, error, critical)");
+        }
+        if let Some(color) = template.color.as_deref().filter(|c| !c.is_empty()) {
+            crate::message::parse_color(color)
+                .map_err(|e| anyhow!("{} in [{section}.levels.{level}]", e))?;
+        }
+        if let Some(mention) = template.mention.as_deref().filter(|m| !m.is_empty()) {
+            crate::message::mention_markup(mention)
+                .map_err(|e| anyhow!("{} in [{section}.levels.{level}]", e))?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_content_rules(
+    section: &str,
+    deny: Option<&[String]>,
+    require: Option<&[String]>,
+) -> Result<()> {
+    ContentRules::new(deny.unwrap_or_default(), require.unwrap_or_default())
+        .map_err(|e| anyhow!("{e} in [{section}]"))?;
+    Ok(())
+}
+
+/// The label is printed raw to the terminal, so escape sequences are refused.
+fn validate_label(section: &str, label: Option<&str>, color: Option<&str>) -> Result<()> {
+    if let Some(label) = label {
+        if label.trim().is_empty() || label.chars().any(char::is_control) {
+            bail!("invalid label '{}' in [{section}] (must be non-empty and contain no control characters)", label.escape_debug());
+        }
+    }
+    if let Some(color) = color {
+        color
+            .parse::<LabelColor>()
+            .map_err(|e| anyhow!("{e} in [{section}]"))?;
+    }
     Ok(())
 }
 
+/// Whether `profile` sets `require_interactive`; used to refuse it in headless mode,
+/// where the profile's settings are otherwise ignored.
+pub fn requires_interactive(config: &ConfigFile, profile: &str) -> bool {
+    config
+        .profiles
+        .get(profile)
+        .and_then(|p| p.require_interactive)
+        .unwrap_or(false)
+}
+
+/// Check the `<seconds>.<micros>` shape of a Slack message timestamp.
+pub fn is_message_ts(ts: &str) -> bool {
+    ts.split_once('.').is_some_and(|(secs, micros)| {
+        !secs.is_empty()
+            && !micros.is_empty()
+            && secs.bytes().all(|b| b.is_ascii_digit())
+            && micros.bytes().all(|b| b.is_ascii_digit())
+    })
+}
+
 fn validate_section_values(
     section: &str,
     output: Option<&str>,
@@ -174,15 +945,14 @@ mod tests {
 
     fn minimal_config() -> ConfigFile {
         ConfigFile {
+            include: Vec::new(),
             default: DefaultConfig {
                 channel: Some("#general".to_string()),
-                max_file_size: None,
-                confirm: None,
-                output: None,
-                search_types: None,
-                token_store: None,
+                ..DefaultConfig::default()
             },
             profiles: HashMap::new(),
+            groups: HashMap::new(),
+            policy: PolicyConfig::default(),
         }
     }
 
@@ -232,17 +1002,391 @@ mod tests {
         cfg.profiles.insert(
             "work".to_string(),
             Profile {
-                channel: None,
-                max_file_size: None,
-                confirm: None,
                 output: Some("xml".to_string()),
-                search_types: None,
+                ..Profile::default()
             },
         );
         let err = validate_config(&cfg).unwrap_err();
         assert!(err.to_string().contains("profiles.work"));
     }
 
+    #[test]
+    fn profile_thread_must_be_message_ts() {
+        let mut cfg = minimal_config();
+        cfg.profiles.insert(
+            "work".to_string(),
+            Profile {
+                thread: Some("yesterday".to_string()),
+                ..Profile::default()
+            },
+        );
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid thread in [profiles.work]"));
+
+        cfg.profiles.get_mut("work").unwrap().thread = Some("1712345678.123456".to_string());
+        validate_config(&cfg).unwrap();
+    }
+
+    #[test]
+    fn profile_users_are_validated() {
+        let mut cfg = minimal_config();
+        cfg.profiles.insert(
+            "oncall".to_string(),
+            Profile {
+                users: Some(vec!["U0123ABCD".to_string(), "W0456EFGH".to_string()]),
+                ..Profile::default()
+            },
+        );
+        validate_config(&cfg).unwrap();
+
+        let profile = cfg.profiles.get_mut("oncall").unwrap();
+        profile.users = Some(vec!["alice".to_string()]);
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("invalid user 'alice'"));
+
+        let profile = cfg.profiles.get_mut("oncall").unwrap();
+        profile.users = Some(Vec::new());
+        assert!(validate_config(&cfg).is_err());
+
+        let profile = cfg.profiles.get_mut("oncall").unwrap();
+        profile.users = Some(vec!["U0123ABCD".to_string()]);
+        profile.channel = Some("#ops".to_string());
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("both channel and users"));
+    }
+
+    #[test]
+    fn retry_delay_values() {
+        let mut cfg = minimal_config();
+        cfg.default.retry_delay = Some("500ms".to_string());
+        validate_config(&cfg).unwrap();
+        cfg.default.retry_delay = Some("soon".to_string());
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("invalid retry_delay in [default]"));
+    }
+
+    #[test]
+    fn timeout_values() {
+        let mut cfg = minimal_config();
+        cfg.default.timeout = Some("0".to_string());
+        validate_config(&cfg).unwrap();
+        cfg.profiles.insert(
+            "cron".to_string(),
+            Profile {
+                timeout: Some("10 minutes".to_string()),
+                ..Profile::default()
+            },
+        );
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid timeout in [profiles.cron]"));
+    }
+
+    #[test]
+    fn proxy_values() {
+        let mut cfg = minimal_config();
+        cfg.default.proxy = Some("http://proxy.corp:8080".to_string());
+        validate_config(&cfg).unwrap();
+        cfg.default.proxy = Some("socks5://proxy.corp".to_string());
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("unsupported proxy scheme"));
+        assert!(err.to_string().contains("[default]"));
+    }
+
+    #[test]
+    fn long_text_values() {
+        let mut cfg = minimal_config();
+        cfg.default.long_text = Some("truncate-head".to_string());
+        validate_config(&cfg).unwrap();
+        cfg.profiles.insert(
+            "ci".to_string(),
+            Profile {
+                long_text: Some("truncate".to_string()),
+                ..Profile::default()
+            },
+        );
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("invalid long_text 'truncate'"));
+        assert!(err.to_string().contains("profiles.ci"));
+    }
+
+    #[test]
+    fn dnd_check_values() {
+        let mut cfg = minimal_config();
+        cfg.default.dnd_check = Some("Confirm".to_string());
+        validate_config(&cfg).unwrap();
+        cfg.profiles.insert(
+            "dm".to_string(),
+            Profile {
+                dnd_check: Some("block".to_string()),
+                ..Profile::default()
+            },
+        );
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("invalid dnd_check 'block'"));
+        assert!(err.to_string().contains("profiles.dm"));
+    }
+
+    #[test]
+    fn shared_check_values() {
+        let mut cfg = minimal_config();
+        cfg.default.shared_check = Some("Deny".to_string());
+        validate_config(&cfg).unwrap();
+        cfg.profiles.insert(
+            "partners".to_string(),
+            Profile {
+                shared_check: Some("block".to_string()),
+                ..Profile::default()
+            },
+        );
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("invalid shared_check 'block'"));
+        assert!(err.to_string().contains("profiles.partners"));
+    }
+
+    #[test]
+    fn profile_channel_pin_is_validated() {
+        let mut cfg = minimal_config();
+        cfg.profiles.insert(
+            "prod".to_string(),
+            Profile {
+                channel_id: Some("C0123ABCD".to_string()),
+                channel_name: Some("#prod-alerts".to_string()),
+                ..Profile::default()
+            },
+        );
+        validate_config(&cfg).unwrap();
+
+        let profile = cfg.profiles.get_mut("prod").unwrap();
+        profile.channel_id = Some("#prod-alerts".to_string());
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid channel_id '#prod-alerts'"));
+
+        let profile = cfg.profiles.get_mut("prod").unwrap();
+        profile.channel_id = None;
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("channel_name in [profiles.prod] requires channel_id"));
+
+        let profile = cfg.profiles.get_mut("prod").unwrap();
+        profile.channel_id = Some("C0123ABCD".to_string());
+        profile.channel = Some("#ops".to_string());
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("both channel and channel_id"));
+    }
+
+    #[test]
+    fn profile_destinations_are_validated() {
+        let mut cfg = minimal_config();
+        let destinations = |pairs: &[(&str, &str)]| {
+            Some(
+                pairs
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            )
+        };
+        cfg.profiles.insert(
+            "svc".to_string(),
+            Profile {
+                destinations: destinations(&[("alerts", "#alerts"), ("reports", "C0123ABCD")]),
+                ..Profile::default()
+            },
+        );
+        validate_config(&cfg).unwrap();
+
+        let profile = cfg.profiles.get_mut("svc").unwrap();
+        profile.channel = Some("#ops".to_string());
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("sets destinations along with channel"));
+
+        let profile = cfg.profiles.get_mut("svc").unwrap();
+        profile.channel = None;
+        profile.destinations = destinations(&[("alerts", "#")]);
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("empty name or channel ('alerts')"));
+    }
+
+    #[test]
+    fn profile_channels_are_validated() {
+        let mut cfg = minimal_config();
+        let channels = |list: &[&str]| Some(list.iter().map(|c| c.to_string()).collect());
+        cfg.profiles.insert(
+            "announce".to_string(),
+            Profile {
+                channels: channels(&["#a", "C0123ABCD"]),
+                ..Profile::default()
+            },
+        );
+        validate_config(&cfg).unwrap();
+
+        let profile = cfg.profiles.get_mut("announce").unwrap();
+        profile.users = Some(vec!["U0123ABCD".to_string()]);
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(
+            err.to_string().contains("sets channels along with channel"),
+            "{err}"
+        );
+
+        let profile = cfg.profiles.get_mut("announce").unwrap();
+        profile.users = None;
+        profile.channels = channels(&["#a", "#b", "#a"]);
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("lists '#a' twice"), "{err}");
+
+        let profile = cfg.profiles.get_mut("announce").unwrap();
+        profile.channels = channels(&[]);
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("channels in [profiles.announce] is empty"));
+    }
+
+    #[test]
+    fn groups_must_list_defined_profiles() {
+        let mut cfg = minimal_config();
+        for name in ["prod-a", "prod-b"] {
+            cfg.profiles.insert(name.to_string(), Profile::default());
+        }
+        let group = |members: &[&str]| members.iter().map(|m| m.to_string()).collect();
+        cfg.groups
+            .insert("release".to_string(), group(&["prod-a", "prod-b"]));
+        validate_config(&cfg).unwrap();
+        assert_eq!(
+            group_members(&cfg, "release").unwrap(),
+            ["prod-a", "prod-b"]
+        );
+        let err = group_members(&cfg, "nightly").unwrap_err();
+        assert_eq!(err.to_string(), "group 'nightly' not found in config");
+
+        cfg.groups
+            .insert("release".to_string(), group(&["prod-a", "prod-c"]));
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("lists 'prod-c', which is not a defined profile"),
+            "{err}"
+        );
+
+        cfg.groups
+            .insert("release".to_string(), group(&["prod-a", "prod-a"]));
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("lists 'prod-a' twice"), "{err}");
+
+        cfg.groups.insert("release".to_string(), group(&[]));
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("group 'release' in [groups] is empty"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn label_values() {
+        let mut cfg = minimal_config();
+        cfg.default.label = Some("staging".to_string());
+        cfg.default.label_color = Some("Yellow".to_string());
+        validate_config(&cfg).unwrap();
+
+        cfg.profiles.insert(
+            "prod".to_string(),
+            Profile {
+                label: Some("prod\u{1b}[0m".to_string()),
+                ..Profile::default()
+            },
+        );
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(
+            err.to_string().contains("invalid label 'prod\\u{1b}[0m'"),
+            "{err}"
+        );
+        assert!(err.to_string().contains("profiles.prod"));
+
+        cfg.profiles.get_mut("prod").unwrap().label = Some(" ".to_string());
+        assert!(validate_config(&cfg).is_err());
+
+        let prod = cfg.profiles.get_mut("prod").unwrap();
+        prod.label = Some("production".to_string());
+        prod.label_color = Some("orange".to_string());
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("invalid label_color 'orange'"));
+    }
+
+    #[test]
+    fn quiet_hours_values() {
+        let mut cfg = minimal_config();
+        cfg.default.quiet_hours = Some("22:00-07:00 +09:00".to_string());
+        cfg.default.quiet_hours_action = Some("Defer".to_string());
+        validate_config(&cfg).unwrap();
+        cfg.profiles.insert(
+            "oncall".to_string(),
+            Profile {
+                quiet_hours: Some("22:00-07:00 JST".to_string()),
+                ..Profile::default()
+            },
+        );
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("invalid quiet_hours"));
+        assert!(err.to_string().contains("profiles.oncall"));
+    }
+
+    #[test]
+    fn levels_are_validated() {
+        let mut cfg: ConfigFile = toml::from_str(
+            r##"
+[default.levels.critical]
+mention = "here"
+color = "#ff0000"
+
+[profiles.ops.levels.error]
+emoji = ":fire:"
+"##,
+        )
+        .unwrap();
+        validate_config(&cfg).unwrap();
+
+        cfg.default
+            .levels
+            .as_mut()
+            .unwrap()
+            .insert("fatal".to_string(), LevelTemplate::default());
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("invalid level 'fatal'"));
+
+        let levels = cfg
+            .profiles
+            .get_mut("ops")
+            .unwrap()
+            .levels
+            .as_mut()
+            .unwrap();
+        levels.get_mut("error").unwrap().mention = Some("@bob".to_string());
+        cfg.default.levels = None;
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("in [profiles.ops.levels.error]"));
+    }
+
+    #[test]
+    fn fallback_token_profile_must_exist() {
+        let mut cfg = minimal_config();
+        cfg.default.fallback_token_profile = Some("backup".to_string());
+        let err = validate_config(&cfg).unwrap_err();
+        assert!(err.to_string().contains("fallback_token_profile 'backup'"));
+
+        cfg.profiles
+            .insert("backup".to_string(), Profile::default());
+        assert!(validate_config(&cfg).is_ok());
+    }
+
     #[test]
     fn valid_token_store_file() {
         for val in &["file", "FILE"] {
@@ -336,11 +1480,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn config_path_prefers_config_dir_over_legacy() {
+        let dir = tempfile::tempdir().unwrap();
+        let xdg = dir.path().join("xdg");
+        let legacy = dir.path().join("home").join(".config");
+        let xdg_file = xdg.join("slafling").join("config.toml");
+        let legacy_file = legacy.join("slafling").join("config.toml");
+
+        // Neither exists: new installs go to the config dir
+        assert_eq!(pick_config_path(Some(&xdg), &legacy), xdg_file);
+
+        // Only the old location exists: keep using it
+        write_config(&legacy_file, "").unwrap();
+        assert_eq!(pick_config_path(Some(&xdg), &legacy), legacy_file);
+
+        // Both exist: the config dir wins
+        write_config(&xdg_file, "").unwrap();
+        assert_eq!(pick_config_path(Some(&xdg), &legacy), xdg_file);
+
+        assert_eq!(pick_config_path(None, &legacy), legacy_file);
+    }
+
     #[test]
     fn init_writes_config_file() {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("config.toml");
-        write_init_config(&path).unwrap();
+        write_config(&path, &generate_init_config()).unwrap();
         assert!(path.exists());
         let content = std::fs::read_to_string(&path).unwrap();
         assert!(content.contains("[default]"));
@@ -351,7 +1517,7 @@ mod tests {
     fn init_creates_parent_dirs() {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("a").join("b").join("config.toml");
-        write_init_config(&path).unwrap();
+        write_config(&path, &generate_init_config()).unwrap();
         assert!(path.exists());
     }
 
@@ -360,12 +1526,136 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("config.toml");
         std::fs::write(&path, "old content").unwrap();
-        write_init_config(&path).unwrap();
+        write_config(&path, &generate_init_config()).unwrap();
         let content = std::fs::read_to_string(&path).unwrap();
         assert!(content.contains("[default]"));
         assert!(!content.contains("old content"));
     }
 
+    #[test]
+    fn include_merges_profiles_with_main_file_winning() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "include = [\"profiles.d/*.toml\"]\n[default]\nchannel = \"#general\"\n[profiles.ops]\nchannel = \"#ops-main\"\n",
+        )
+        .unwrap();
+        write_config(
+            &dir.path().join("profiles.d").join("a.toml"),
+            "[profiles.ci]\nchannel = \"#ci-a\"\n[profiles.ops]\nchannel = \"#ops-a\"\n",
+        )
+        .unwrap();
+        write_config(
+            &dir.path().join("profiles.d").join("b.toml"),
+            "[profiles.ci]\nchannel = \"#ci-b\"\n",
+        )
+        .unwrap();
+
+        let cfg = load_config_at(&path).unwrap();
+        assert_eq!(cfg.profiles["ci"].channel.as_deref(), Some("#ci-b"));
+        assert_eq!(cfg.profiles["ops"].channel.as_deref(), Some("#ops-main"));
+    }
+
+    #[test]
+    fn include_rejects_sections_other_than_profiles() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "include = [\"extra.toml\"]\n[default]\n").unwrap();
+        std::fs::write(
+            dir.path().join("extra.toml"),
+            "[policy]\ndenied_channels = []\n",
+        )
+        .unwrap();
+        let Err(err) = load_config_at(&path) else {
+            panic!("expected an error");
+        };
+        assert!(
+            format!("{err:#}").contains("unknown field `policy`"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn include_of_missing_plain_path_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "include = [\"nope.toml\", \"empty.d/*.toml\"]\n[default]\n",
+        )
+        .unwrap();
+        let Err(err) = load_config_at(&path) else {
+            panic!("expected an error");
+        };
+        assert!(err.to_string().contains("nope.toml not found"), "{err}");
+    }
+
+    #[test]
+    fn backup_config_copies_existing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[default]\nchannel = \"#tuned\"\n").unwrap();
+        let backup = backup_config(&path).unwrap();
+        assert_eq!(backup.parent(), path.parent());
+        let name = backup.file_name().unwrap().to_string_lossy().into_owned();
+        assert!(name.starts_with("config.toml.bak-"), "got {name}");
+        assert_eq!(
+            std::fs::read_to_string(&backup).unwrap(),
+            std::fs::read_to_string(&path).unwrap()
+        );
+    }
+
+    #[test]
+    fn from_env_sets_default_values() {
+        let env = Env {
+            channel: Some("#deploy".to_string()),
+            max_file_size: Some("50MB".to_string()),
+            confirm: Some("yes".to_string()),
+            output: Some("JSON".to_string()),
+            search_types: Some("im, mpim".to_string()),
+            ..Env::default()
+        };
+        let content = generate_config_from_env(&env).unwrap();
+        let parsed: ConfigFile = toml::from_str(&content).unwrap();
+        assert_eq!(parsed.default.channel.as_deref(), Some("#deploy"));
+        assert_eq!(parsed.default.max_file_size.as_deref(), Some("50MB"));
+        assert_eq!(parsed.default.confirm, Some(true));
+        assert_eq!(parsed.default.output.as_deref(), Some("json"));
+        assert_eq!(
+            parsed.default.search_types,
+            Some(vec!["im".to_string(), "mpim".to_string()])
+        );
+        assert!(validate_config(&parsed).is_ok());
+    }
+
+    #[test]
+    fn from_env_without_vars_matches_template() {
+        let content = generate_config_from_env(&Env::default()).unwrap();
+        assert_eq!(content, generate_init_config());
+    }
+
+    #[test]
+    fn from_env_escapes_channel() {
+        let env = Env {
+            channel: Some("#a\"b".to_string()),
+            ..Env::default()
+        };
+        let content = generate_config_from_env(&env).unwrap();
+        let parsed: ConfigFile = toml::from_str(&content).unwrap();
+        assert_eq!(parsed.default.channel.as_deref(), Some("#a\"b"));
+    }
+
+    #[test]
+    fn from_env_rejects_invalid_output() {
+        let env = Env {
+            output: Some("yaml".to_string()),
+            ..Env::default()
+        };
+        let err = generate_config_from_env(&env).unwrap_err();
+        assert!(err.to_string().contains("SLAFLING_OUTPUT"));
+    }
+
     #[test]
     fn init_config_has_no_token_field() {
         let content = generate_init_config();
@@ -1,9 +1,21 @@
+mod edit;
 mod env;
 mod file;
+mod policy;
 mod resolved;
+mod schema;
+mod strict;
 mod util;
 
+pub use edit::ConfigDocument;
 pub use env::Env;
-pub use file::{config_path, load_config, resolve_token_store, write_init_config, TokenStore};
-pub use resolved::{describe_token_source, Config, ResolvedConfig};
+pub use file::{
+    backup_config, config_path, generate_config_from_env, generate_init_config, group_members,
+    is_message_ts, load_config, load_host_policy, requires_interactive, resolve_token_store,
+    set_config_path, write_config, ConfigFile, DndCheck, LabelColor, LevelTemplate,
+    QuietHoursAction, SecretScan, SharedCheck, TokenStore,
+};
+pub use resolved::{describe_token_source, resolve_token, stored_token, Config, ResolvedConfig};
+pub use schema::config_schema;
+pub use strict::unknown_keys;
 pub use util::format_size;
@@ -0,0 +1,225 @@
+use anyhow::{anyhow, bail, Result};
+use glob::{MatchOptions, Pattern};
+use regex::Regex;
+use serde::Deserialize;
+
+/// `[policy]`: host-wide limits on where any profile may post.
+#[derive(Clone, Default, Deserialize)]
+pub struct PolicyConfig {
+    pub allowed_channels: Option<Vec<String>>,
+    pub denied_channels: Option<Vec<String>>,
+    /// Refuse Slack Connect destinations whatever the profile's `shared_check`
+    pub deny_shared_channels: Option<bool>,
+}
+
+/// Compiled `[policy]` patterns. Channels are compared without the leading `#` and
+/// case-insensitively; DMs and group DMs are matched by their conversation or user ID.
+#[derive(Clone, Debug, Default)]
+pub struct ChannelPolicy {
+    allowed: Vec<Pattern>,
+    denied: Vec<Pattern>,
+    deny_shared: bool,
+}
+
+const MATCH: MatchOptions = MatchOptions {
+    case_sensitive: false,
+    require_literal_separator: false,
+    require_literal_leading_dot: false,
+};
+
+fn compile(key: &str, patterns: Option<&[String]>) -> Result<Vec<Pattern>> {
+    patterns
+        .unwrap_or_default()
+        .iter()
+        .map(|p| {
+            Pattern::new(p.trim_start_matches('#'))
+                .map_err(|e| anyhow!("invalid pattern '{p}' in [policy] {key}: {}", e.msg))
+        })
+        .collect()
+}
+
+impl ChannelPolicy {
+    pub fn new(config: &PolicyConfig) -> Result<Self> {
+        Ok(Self {
+            allowed: compile("allowed_channels", config.allowed_channels.as_deref())?,
+            denied: compile("denied_channels", config.denied_channels.as_deref())?,
+            deny_shared: config.deny_shared_channels.unwrap_or(false),
+        })
+    }
+
+    /// Whether `deny_shared_channels` is set.
+    pub fn denies_shared(&self) -> bool {
+        self.deny_shared
+    }
+
+    /// Whether any channel patterns are set, i.e. whether `check` can refuse a channel.
+    pub fn limits_channels(&self) -> bool {
+        !self.allowed.is_empty() || !self.denied.is_empty()
+    }
+
+    /// Refuse `channel` if it matches `denied_channels`, or if `allowed_channels` is set
+    /// and it matches none of them. Denial wins over allowance. `name` is the channel's
+    /// name when `channel` is an ID; a pattern matching either the ID or the name counts.
+    pub fn check(&self, channel: &str, name: Option<&str>) -> Result<()> {
+        let candidates: Vec<&str> = std::iter::once(channel)
+            .chain(name)
+            .map(|c| c.trim_start_matches('#'))
+            .collect();
+        let matches = |p: &Pattern| candidates.iter().any(|c| p.matches_with(c, MATCH));
+        let target = match name {
+            Some(name) => format!("{channel} (#{})", name.trim_start_matches('#')),
+            None => channel.to_string(),
+        };
+        if let Some(p) = self.denied.iter().find(|p| matches(p)) {
+            bail!(
+                "posting to {target} is denied by [policy] denied_channels ('{}')",
+                p.as_str()
+            );
+        }
+        if !self.allowed.is_empty() && !self.allowed.iter().any(matches) {
+            bail!("{target} is not in [policy] allowed_channels");
+        }
+        Ok(())
+    }
+}
+
+/// A profile's `deny_patterns` / `require_patterns`, checked against the message text.
+#[derive(Clone, Debug, Default)]
+pub struct ContentRules {
+    deny: Vec<Regex>,
+    require: Vec<Regex>,
+}
+
+fn compile_regexes(key: &str, patterns: &[String]) -> Result<Vec<Regex>> {
+    patterns
+        .iter()
+        .map(|p| Regex::new(p).map_err(|e| anyhow!("invalid regex '{p}' in {key}: {e}")))
+        .collect()
+}
+
+impl ContentRules {
+    pub fn new(deny: &[String], require: &[String]) -> Result<Self> {
+        Ok(Self {
+            deny: compile_regexes("deny_patterns", deny)?,
+            require: compile_regexes("require_patterns", require)?,
+        })
+    }
+
+    /// Refuse `text` if it matches any deny pattern or misses any require pattern.
+    pub fn check(&self, text: &str) -> Result<()> {
+        if let Some(re) = self.deny.iter().find(|re| re.is_match(text)) {
+            bail!("message matches deny_patterns '{}'", re.as_str());
+        }
+        if let Some(re) = self.require.iter().find(|re| !re.is_match(text)) {
+            bail!("message does not match require_patterns '{}'", re.as_str());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn policy(allowed: &[&str], denied: &[&str]) -> ChannelPolicy {
+        let list = |v: &[&str]| (!v.is_empty()).then(|| v.iter().map(|s| s.to_string()).collect());
+        ChannelPolicy::new(&PolicyConfig {
+            allowed_channels: list(allowed),
+            denied_channels: list(denied),
+            ..PolicyConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[rstest]
+    #[case(&[], &[], "#anything", true)]
+    #[case(&[], &["#prod-*"], "#prod-alerts", false)]
+    #[case(&[], &["prod-*"], "#PROD-alerts", false)]
+    #[case(&[], &["#prod-*"], "#staging-alerts", true)]
+    #[case(&["#team-*", "C0123ABCD"], &[], "#team-ops", true)]
+    #[case(&["#team-*", "C0123ABCD"], &[], "C0123ABCD", true)]
+    #[case(&["#team-*"], &[], "#general", false)]
+    #[case(&["#team-*"], &["#team-exec"], "#team-exec", false)]
+    fn check_cases(
+        #[case] allowed: &[&str],
+        #[case] denied: &[&str],
+        #[case] channel: &str,
+        #[case] ok: bool,
+    ) {
+        assert_eq!(policy(allowed, denied).check(channel, None).is_ok(), ok);
+    }
+
+    #[test]
+    fn channel_ids_are_matched_by_name_too() {
+        let err = policy(&[], &["#prod-*"])
+            .check("C0123ABCD", Some("prod-alerts"))
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("posting to C0123ABCD (#prod-alerts) is denied"),
+            "{err}"
+        );
+        let allowed = policy(&["#team-*"], &[]);
+        allowed.check("C0123ABCD", Some("team-ops")).unwrap();
+        assert!(allowed.check("C0123ABCD", Some("general")).is_err());
+    }
+
+    #[test]
+    fn denial_names_the_pattern() {
+        let err = policy(&[], &["#general"])
+            .check("#general", None)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("denied_channels ('general')"),
+            "{err}"
+        );
+    }
+
+    #[rstest]
+    #[case("OPS-123 rotate certs", Ok(()))]
+    #[case(
+        "rotate certs",
+        Err("does not match require_patterns '^[A-Z]+-[0-9]+ '")
+    )]
+    #[case(
+        "OPS-1 drop table users",
+        Err("matches deny_patterns '(?i)drop table'")
+    )]
+    fn content_rules_cases(#[case] text: &str, #[case] expected: Result<(), &str>) {
+        let rules = ContentRules::new(
+            &["(?i)drop table".to_string()],
+            &["^[A-Z]+-[0-9]+ ".to_string()],
+        )
+        .unwrap();
+        match (rules.check(text), expected) {
+            (Ok(()), Ok(())) => {}
+            (Err(e), Err(msg)) => assert!(e.to_string().contains(msg), "{e}"),
+            (got, _) => panic!("unexpected result for {text:?}: {got:?}"),
+        }
+    }
+
+    #[test]
+    fn invalid_regex_is_rejected() {
+        let err = ContentRules::new(&[], &["[A-Z".to_string()]).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("invalid regex '[A-Z' in require_patterns"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn invalid_pattern_is_rejected() {
+        let err = ChannelPolicy::new(&PolicyConfig {
+            denied_channels: Some(vec!["#prod-[".to_string()]),
+            ..PolicyConfig::default()
+        })
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("invalid pattern '#prod-['"),
+            "{err}"
+        );
+    }
+}
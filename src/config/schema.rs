@@ -0,0 +1,210 @@
+use serde_json::{json, Map, Value};
+
+fn string(description: &str) -> Value {
+    json!({ "type": "string", "description": description })
+}
+
+fn boolean(description: &str) -> Value {
+    json!({ "type": "boolean", "description": description })
+}
+
+fn one_of(description: &str, values: &[&str]) -> Value {
+    json!({ "type": "string", "enum": values, "description": description })
+}
+
+fn strings(description: &str) -> Value {
+    json!({ "type": "array", "items": { "type": "string" }, "description": description })
+}
+
+/// Settings allowed in both `[default]` and `[profiles.<name>]`.
+fn shared_settings() -> Vec<(&'static str, Value)> {
+    vec![
+        ("channel", string("Target channel, e.g. \"#general\" or \"C01ABCDEF\"")),
+        ("max_file_size", string("Max file upload size, e.g. \"100MB\" (units: B, KB, MB, GB; default 100MB)")),
+        ("confirm", boolean("Prompt for confirmation before sending")),
+        ("confirm_timeout", string("Abort an unanswered confirmation prompt after this long, e.g. \"30s\" (default \"0\" = wait forever)")),
+        ("protected", boolean("Require typing the channel name before every send, and refuse -y")),
+        ("label", string("Environment name shown as a badge before confirm prompts")),
+        ("label_color", one_of("Background color of the label badge", &["red", "yellow", "green", "blue", "magenta", "cyan"])),
+        ("output", one_of("Output format for search (default table)", &["table", "tsv", "json"])),
+        ("search_types", json!({
+            "type": "array",
+            "items": { "enum": ["public_channel", "private_channel", "im", "mpim"] },
+            "description": "Channel types to search with the search subcommand",
+        })),
+        ("fallback_token_profile", string("Profile whose stored token is used if this token is rejected")),
+        ("required_scopes", strings("Scopes the token must grant; checked by `token set` and `validate --remote`")),
+        ("deny_patterns", strings("Regexes the message text must not match")),
+        ("require_patterns", strings("Regexes the message text must match")),
+        ("history", boolean("Record every successful send to the local history file")),
+        ("unfurl_links", boolean("Link previews for posted URLs")),
+        ("unfurl_media", boolean("Media previews for posted URLs")),
+        ("link_names", boolean("Let @here / @channel / @everyone through instead of neutralizing them")),
+        ("resolve_mentions", boolean("Rewrite @handle into a real mention via users.list")),
+        ("offline_queue", boolean("Queue sends Slack can't be reached for, for `slafling flush`")),
+        ("auto_join", boolean("Join a public channel and retry when a post fails with not_in_channel")),
+        ("fallback_channel", string("Channel that gets the message, with a note, when Slack refuses the primary one")),
+        ("test_channel", string("Channel that gets every send instead with --redirect-to-test")),
+        ("dedupe_ttl", string("How long a --dedupe-key send suppresses repeats, e.g. \"30m\" (default \"1h\")")),
+        ("undo_delay", string("Hold each send this long with a cancellable countdown, e.g. \"10s\" (default \"0\" = off)")),
+        ("min_interval", string("Refuse a send less than this long after the profile's previous one, e.g. \"30s\" (default \"0\" = off)")),
+        ("quiet_hours", string("Daily window during which sends are held back, e.g. \"22:00-07:00 +09:00\"")),
+        ("quiet_hours_action", one_of("What happens to a send during quiet_hours (default block)", &["block", "defer"])),
+        ("strip_exif", boolean("Remove EXIF metadata from .jpg/.jpeg uploads")),
+        ("trim_stdin", boolean("Trim trailing whitespace from stdin text (default true)")),
+        ("dnd_check", one_of("Check a DM recipient's do-not-disturb (default off)", &["off", "warn", "confirm"])),
+        ("shared_check", one_of("Check for Slack Connect destinations (default off)", &["off", "warn", "confirm", "deny"])),
+        ("secret_scan", one_of("Credential check on outgoing text and text uploads (default block)", &["off", "block", "redact"])),
+        ("long_text", one_of("What to do with text over Slack's 4000-character limit", &["error", "truncate-head", "truncate-tail", "split", "upload"])),
+        ("retries", json!({
+            "type": "integer",
+            "minimum": 0,
+            "description": "Retries for rate-limited, 5xx and network failures (default 3)",
+        })),
+        ("retry_delay", string("First retry wait, growing exponentially, e.g. \"500ms\" (default \"1s\")")),
+        ("timeout", string("Network timeout per Slack API call, e.g. \"10s\" (default \"30s\", \"0\" = none)")),
+        ("proxy", string("HTTP(S) proxy for all Slack traffic, e.g. \"http://proxy.corp:8080\"")),
+        ("ca_bundle", string("PEM file of CA certificates to trust instead of the built-in roots")),
+        ("api_base", string("Web API base URL (default \"https://slack.com/api\")")),
+        ("levels", json!({
+            "type": "object",
+            "propertyNames": { "enum": ["info", "warn", "error", "critical"] },
+            "additionalProperties": { "$ref": "#/definitions/level" },
+            "description": "Formatting for --level, per level",
+        })),
+    ]
+}
+
+fn section(description: &str, settings: Vec<(&'static str, Value)>) -> Value {
+    let properties: Map<String, Value> = settings
+        .into_iter()
+        .map(|(key, schema)| (key.to_string(), schema))
+        .collect();
+    json!({
+        "type": "object",
+        "description": description,
+        "properties": properties,
+        "additionalProperties": false,
+    })
+}
+
+/// JSON Schema (draft-07) for the config file, for `config schema`.
+pub fn config_schema() -> Value {
+    let mut default = shared_settings();
+    default.push((
+        "token_store",
+        one_of(
+            "Token storage backend (default keychain on macOS, file elsewhere)",
+            &["keychain", "file"],
+        ),
+    ));
+
+    let mut profile = shared_settings();
+    profile.extend([
+        (
+            "thread",
+            string("Post every message as a reply in this thread (message ts)"),
+        ),
+        (
+            "users",
+            strings("Group DM members (user IDs), used instead of channel"),
+        ),
+        (
+            "channel_id",
+            string("Conversation ID used instead of channel"),
+        ),
+        (
+            "channel_name",
+            string("Name channel_id must still have at send time"),
+        ),
+        (
+            "destinations",
+            json!({
+                "type": "object",
+                "additionalProperties": { "type": "string" },
+                "description": "Named channels picked per send with --dest, used instead of channel",
+            }),
+        ),
+        (
+            "channels",
+            strings("Channels every send is broadcast to, used instead of channel"),
+        ),
+        (
+            "require_interactive",
+            boolean("Refuse -y, non-TTY use and headless mode for this profile"),
+        ),
+    ]);
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "slafling config",
+        "type": "object",
+        "properties": {
+            "include": strings("Files whose [profiles.*] are merged in (globs, relative to this file; ~/ = home)"),
+            "default": { "$ref": "#/definitions/default" },
+            "profiles": {
+                "type": "object",
+                "description": "Per-use-case overrides, selected with --profile / SLAFLING_PROFILE",
+                "additionalProperties": { "$ref": "#/definitions/profile" },
+            },
+            "groups": {
+                "type": "object",
+                "description": "Lists of profiles a send goes through in turn with -p @<group>",
+                "additionalProperties": { "type": "array", "items": { "type": "string" }, "minItems": 1 },
+            },
+            "policy": { "$ref": "#/definitions/policy" },
+        },
+        "required": ["default"],
+        "additionalProperties": false,
+        "definitions": {
+            "default": section("Settings for every profile", default),
+            "profile": section("A profile's settings, layered over [default]", profile),
+            "level": section("Formatting for one --level", vec![
+                ("emoji", string("Emoji prefix, e.g. \":rotating_light:\" (\"\" = none)")),
+                ("color", string("Attachment color, e.g. \"#e01e5a\" or \"danger\" (\"\" = none)")),
+                ("mention", string("here, channel, everyone, a user ID (U…) or a user group ID (S…)")),
+            ]),
+            "policy": section("Host-wide limits checked at send time for every profile", vec![
+                ("allowed_channels", strings("If set, refuse channels matching none of these globs")),
+                ("denied_channels", strings("Refuse channels matching any of these globs")),
+                ("deny_shared_channels", boolean("Refuse Slack Connect destinations whatever a profile's shared_check")),
+            ]),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::file::{ConfigFile, DefaultConfig, LevelTemplate, Profile};
+    use super::super::policy::PolicyConfig;
+    use super::super::strict::fields;
+    use super::*;
+
+    fn keys(schema: &Value) -> Vec<&str> {
+        let mut keys: Vec<&str> = schema["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    fn sorted(fields: &[&'static str]) -> Vec<&'static str> {
+        let mut fields = fields.to_vec();
+        fields.sort();
+        fields
+    }
+
+    #[test]
+    fn schema_covers_every_config_key() {
+        let schema = config_schema();
+        let defs = &schema["definitions"];
+        assert_eq!(keys(&schema), sorted(fields::<ConfigFile>()));
+        assert_eq!(keys(&defs["default"]), sorted(fields::<DefaultConfig>()));
+        assert_eq!(keys(&defs["profile"]), sorted(fields::<Profile>()));
+        assert_eq!(keys(&defs["level"]), sorted(fields::<LevelTemplate>()));
+        assert_eq!(keys(&defs["policy"]), sorted(fields::<PolicyConfig>()));
+    }
+}
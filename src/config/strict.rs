@@ -0,0 +1,201 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::de::{self, DeserializeOwned, Visitor};
+
+use super::file::{include_paths, ConfigFile, DefaultConfig, LevelTemplate, Profile};
+use super::policy::PolicyConfig;
+
+// ── Field names from serde ───────────────────────────────────────────────────
+
+/// Deserializer that records the field list the derive passes to `deserialize_struct`
+/// and then fails, so the known keys always match the structs.
+struct FieldCapture(&'static [&'static str]);
+
+impl<'de> de::Deserializer<'de> for &mut FieldCapture {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0 = fields;
+        Err(de::Error::custom("fields captured"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes
+        byte_buf option unit unit_struct newtype_struct seq tuple tuple_struct map enum
+        identifier ignored_any
+    }
+}
+
+pub(super) fn fields<T: DeserializeOwned>() -> &'static [&'static str] {
+    let mut capture = FieldCapture(&[]);
+    let _ = T::deserialize(&mut capture);
+    capture.0
+}
+
+// ── Unknown keys ─────────────────────────────────────────────────────────────
+
+/// Keys that no setting reads — typos like `chanel` that a normal load silently ignores —
+/// in the config file at `path` and the files it includes.
+pub fn unknown_keys(path: &Path) -> Result<Vec<String>> {
+    let table = read_table(path)?;
+    let mut found = Vec::new();
+    check(&table, fields::<ConfigFile>(), "", &mut found);
+    if let Some(default) = table.get("default").and_then(toml::Value::as_table) {
+        check_section(default, fields::<DefaultConfig>(), "default", &mut found);
+    }
+    if let Some(policy) = table.get("policy").and_then(toml::Value::as_table) {
+        check(policy, fields::<PolicyConfig>(), "policy", &mut found);
+    }
+    check_profiles(&table, &mut found);
+
+    let base = path.parent().unwrap_or(Path::new("."));
+    let patterns = table.get("include").and_then(toml::Value::as_array);
+    for pattern in patterns
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_str)
+    {
+        for file in include_paths(base, pattern)? {
+            if file != path {
+                let mut in_file = Vec::new();
+                check_profiles(&read_table(&file)?, &mut in_file);
+                found.extend(in_file.iter().map(|f| format!("{}: {f}", file.display())));
+            }
+        }
+    }
+    Ok(found)
+}
+
+fn read_table(path: &Path) -> Result<toml::Table> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    toml::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+fn check_profiles(table: &toml::Table, found: &mut Vec<String>) {
+    let profiles = table.get("profiles").and_then(toml::Value::as_table);
+    for (name, profile) in profiles.into_iter().flatten() {
+        if let Some(profile) = profile.as_table() {
+            let section = format!("profiles.{name}");
+            check_section(profile, fields::<Profile>(), &section, found);
+        }
+    }
+}
+
+/// A `[default]` or profile table, including its `levels.<level>` tables.
+fn check_section(table: &toml::Table, known: &[&str], section: &str, found: &mut Vec<String>) {
+    check(table, known, section, found);
+    let levels = table.get("levels").and_then(toml::Value::as_table);
+    for (level, template) in levels.into_iter().flatten() {
+        if let Some(template) = template.as_table() {
+            let section = format!("{section}.levels.{level}");
+            check(template, fields::<LevelTemplate>(), &section, found);
+        }
+    }
+}
+
+fn check(table: &toml::Table, known: &[&str], section: &str, found: &mut Vec<String>) {
+    for key in table.keys() {
+        if known.contains(&key.as_str()) {
+            continue;
+        }
+        let path = if section.is_empty() {
+            key.clone()
+        } else {
+            format!("{section}.{key}")
+        };
+        match closest(key, known) {
+            Some(hint) => found.push(format!("unknown key '{path}' (did you mean '{hint}'?)")),
+            None => found.push(format!("unknown key '{path}'")),
+        }
+    }
+}
+
+/// The known key within two edits of `key`, if any.
+fn closest<'a>(key: &str, known: &[&'a str]) -> Option<&'a str> {
+    known
+        .iter()
+        .map(|k| (edit_distance(key, k), *k))
+        .filter(|(d, _)| *d <= 2)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, k)| k)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            row.push((prev[j] + cost).min(prev[j + 1] + 1).min(row[j] + 1));
+        }
+        prev = row;
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unknown(content: &str) -> Vec<String> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, content).unwrap();
+        unknown_keys(&path).unwrap()
+    }
+
+    #[test]
+    fn known_keys_pass() {
+        let found = unknown(
+            "include = []\n[default]\nchannel = \"#a\"\n[default.levels.error]\nemoji = \":x:\"\n\
+             [profiles.ops]\nchannel = \"#ops\"\nconfirm = true\n[policy]\ndenied_channels = []\n",
+        );
+        assert!(found.is_empty(), "{found:?}");
+    }
+
+    #[test]
+    fn typos_are_reported_with_a_hint() {
+        let found = unknown(
+            "[default]\nchanel = \"#a\"\n[profiles.ops]\nconfrim = true\nfoo = 1\n\
+             [profiles.ops.levels.warn]\ncolour = \"warning\"\n[polcy]\n",
+        );
+        assert_eq!(
+            found,
+            vec![
+                "unknown key 'polcy' (did you mean 'policy'?)",
+                "unknown key 'default.chanel' (did you mean 'channel'?)",
+                "unknown key 'profiles.ops.confrim' (did you mean 'confirm'?)",
+                "unknown key 'profiles.ops.foo'",
+                "unknown key 'profiles.ops.levels.warn.colour' (did you mean 'color'?)",
+            ]
+        );
+    }
+
+    #[test]
+    fn included_profiles_are_checked() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "include = [\"*.toml\"]\n[default]\n").unwrap();
+        let extra = dir.path().join("extra.toml");
+        std::fs::write(&extra, "[profiles.ci]\nchanell = \"#ci\"\n").unwrap();
+        assert_eq!(
+            unknown_keys(&path).unwrap(),
+            vec![format!(
+                "{}: unknown key 'profiles.ci.chanell' (did you mean 'channel'?)",
+                extra.display()
+            )]
+        );
+    }
+}
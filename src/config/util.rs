@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use anyhow::{bail, Context, Result};
 
 pub(super) const KB: u64 = 1_024;
@@ -44,6 +46,34 @@ pub fn format_size(bytes: u64) -> String {
     }
 }
 
+/// Parse a delay like `500ms`, `2s`, `1m` or `6h` (a bare number is seconds).
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let (num_part, unit) = match s.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(i) => (s[..i].trim(), s[i..].trim().to_ascii_lowercase()),
+        None => (s, String::new()),
+    };
+    let num: f64 = num_part
+        .parse()
+        .with_context(|| format!("invalid number in duration: '{s}'"))?;
+    if !num.is_finite() || num < 0.0 {
+        bail!("invalid duration: '{s}'");
+    }
+    let secs = match unit.as_str() {
+        "ms" => num / 1000.0,
+        "" | "s" => num,
+        "m" => num * 60.0,
+        "h" => num * 3600.0,
+        _ => bail!("unknown duration unit: '{unit}' (use ms, s, m, or h)"),
+    };
+    Ok(Duration::from_secs_f64(secs))
+}
+
+/// A `timeout` value: a duration, where 0 means no limit.
+pub(super) fn parse_timeout(s: &str) -> Result<Option<Duration>> {
+    Ok(Some(parse_duration(s)?).filter(|d| !d.is_zero()))
+}
+
 pub(super) fn is_truthy(s: &str) -> bool {
     matches!(s.to_lowercase().as_str(), "1" | "true" | "yes")
 }
@@ -54,6 +84,26 @@ mod tests {
 
     use super::*;
 
+    #[rstest]
+    #[case("500ms", Duration::from_millis(500))]
+    #[case("2s", Duration::from_secs(2))]
+    #[case("2", Duration::from_secs(2))]
+    #[case("1.5S", Duration::from_millis(1500))]
+    #[case("1m", Duration::from_secs(60))]
+    #[case("6h", Duration::from_secs(6 * 3600))]
+    fn parse_duration_valid(#[case] input: &str, #[case] expected: Duration) {
+        assert_eq!(parse_duration(input).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("-1s")]
+    #[case("5d")]
+    #[case("fast")]
+    fn parse_duration_invalid(#[case] input: &str) {
+        assert!(parse_duration(input).is_err());
+    }
+
     #[test]
     fn is_truthy_values() {
         for val in &["1", "true", "yes", "TRUE", "Yes", "YES"] {
@@ -0,0 +1,124 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// A dedupe key sent recently; a send with the same key is skipped until `expires_at`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Entry {
+    /// Profile used (`None` = default); keys are scoped per profile
+    profile: Option<String>,
+    key: String,
+    /// Unix time the suppression ends
+    expires_at: i64,
+}
+
+pub fn state_path() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir().context("could not determine data directory")?;
+    Ok(data_dir.join("slafling").join("dedupe.json"))
+}
+
+fn read(path: &Path) -> Result<Vec<Entry>> {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+    };
+    serde_json::from_str(&json).with_context(|| format!("{}: invalid dedupe state", path.display()))
+}
+
+/// When an earlier send with `key` stops suppressing new ones, if it still does at `now`.
+pub fn suppressed_until(
+    path: &Path,
+    profile: Option<&str>,
+    key: &str,
+    now: i64,
+) -> Result<Option<i64>> {
+    Ok(read(path)?
+        .into_iter()
+        .find(|e| e.profile.as_deref() == profile && e.key == key && e.expires_at > now)
+        .map(|e| e.expires_at))
+}
+
+/// Remember that `key` was sent, replacing any earlier entry and dropping expired ones.
+pub fn record(
+    path: &Path,
+    profile: Option<&str>,
+    key: &str,
+    now: i64,
+    ttl_secs: i64,
+) -> Result<()> {
+    let mut entries: Vec<Entry> = read(path)?
+        .into_iter()
+        .filter(|e| e.expires_at > now && !(e.profile.as_deref() == profile && e.key == key))
+        .collect();
+    entries.push(Entry {
+        profile: profile.map(String::from),
+        key: key.to_string(),
+        expires_at: now + ttl_secs,
+    });
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+    // Write then rename, so a concurrent run never reads a half-written file
+    let tmp = path.with_extension(format!("json.{}", std::process::id()));
+    std::fs::write(&tmp, serde_json::to_string(&entries)?)
+        .with_context(|| format!("failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suppresses_until_ttl_expires() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("slafling").join("dedupe.json");
+        assert_eq!(
+            suppressed_until(&path, None, "disk-full", 1000).unwrap(),
+            None
+        );
+
+        record(&path, None, "disk-full", 1000, 600).unwrap();
+        assert_eq!(
+            suppressed_until(&path, None, "disk-full", 1300).unwrap(),
+            Some(1600)
+        );
+        assert_eq!(
+            suppressed_until(&path, None, "disk-full", 1600).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn keys_are_scoped_per_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dedupe.json");
+        record(&path, Some("ci"), "deploy", 1000, 600).unwrap();
+        assert!(suppressed_until(&path, Some("ci"), "deploy", 1001)
+            .unwrap()
+            .is_some());
+        assert!(suppressed_until(&path, None, "deploy", 1001)
+            .unwrap()
+            .is_none());
+        assert!(suppressed_until(&path, Some("ci"), "backup", 1001)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn record_replaces_and_prunes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dedupe.json");
+        record(&path, None, "a", 1000, 60).unwrap();
+        record(&path, None, "b", 1000, 600).unwrap();
+        record(&path, None, "b", 1100, 600).unwrap();
+        let entries = read(&path).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, "b");
+        assert_eq!(entries[0].expires_at, 1700);
+    }
+}
@@ -0,0 +1,184 @@
+use std::io::{self, Read};
+
+const SOI: u8 = 0xD8;
+const EOI: u8 = 0xD9;
+const SOS: u8 = 0xDA;
+const APP1: u8 = 0xE1;
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+
+/// Whether `strip_exif` applies to a file with this name.
+pub fn is_jpeg_name(name: &str) -> bool {
+    let ext = name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase());
+    matches!(ext.as_deref(), Some("jpg" | "jpeg" | "jpe"))
+}
+
+enum State {
+    Start,
+    Segments,
+    /// Entropy-coded image data and everything after it is copied as is
+    Passthrough,
+}
+
+/// A reader yielding a JPEG with its EXIF (APP1 `Exif`) segments removed.
+/// Only the header segments are buffered; the image data is streamed through.
+pub struct ExifStripper<R> {
+    inner: R,
+    state: State,
+    pending: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: Read> ExifStripper<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            state: State::Start,
+            pending: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    fn read_byte(&mut self) -> io::Result<u8> {
+        let mut b = [0u8; 1];
+        self.inner.read_exact(&mut b)?;
+        Ok(b[0])
+    }
+
+    /// Parse the next marker into `pending`, dropping it if it is an EXIF segment.
+    fn next_segment(&mut self) -> io::Result<()> {
+        if self.read_byte()? != 0xFF {
+            return Err(invalid("expected a JPEG marker"));
+        }
+        let mut marker = self.read_byte()?;
+        // Any number of 0xFF fill bytes may precede the marker code
+        while marker == 0xFF {
+            marker = self.read_byte()?;
+        }
+
+        self.pending.clear();
+        self.pos = 0;
+        if marker == SOS || marker == EOI {
+            self.pending.extend([0xFF, marker]);
+            self.state = State::Passthrough;
+            return Ok(());
+        }
+        // RSTn and TEM carry no length
+        if (0xD0..=0xD7).contains(&marker) || marker == 0x01 {
+            self.pending.extend([0xFF, marker]);
+            return Ok(());
+        }
+
+        let mut len = [0u8; 2];
+        self.inner.read_exact(&mut len)?;
+        let payload_len = usize::from(u16::from_be_bytes(len))
+            .checked_sub(2)
+            .ok_or_else(|| invalid("bad JPEG segment length"))?;
+        let mut payload = vec![0u8; payload_len];
+        self.inner.read_exact(&mut payload)?;
+        if marker == APP1 && payload.starts_with(EXIF_HEADER) {
+            return Ok(());
+        }
+        self.pending.extend([0xFF, marker, len[0], len[1]]);
+        self.pending.extend(payload);
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for ExifStripper<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if self.pos < self.pending.len() {
+                let n = buf.len().min(self.pending.len() - self.pos);
+                buf[..n].copy_from_slice(&self.pending[self.pos..self.pos + n]);
+                self.pos += n;
+                return Ok(n);
+            }
+            match self.state {
+                State::Start => {
+                    let mut soi = [0u8; 2];
+                    self.inner.read_exact(&mut soi)?;
+                    if soi != [0xFF, SOI] {
+                        return Err(invalid("not a JPEG file (strip_exif is set)"));
+                    }
+                    self.pending = soi.to_vec();
+                    self.pos = 0;
+                    self.state = State::Segments;
+                }
+                State::Segments => self.next_segment()?,
+                State::Passthrough => return self.inner.read(buf),
+            }
+        }
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn segment(marker: u8, payload: &[u8]) -> Vec<u8> {
+        let len = (payload.len() + 2) as u16;
+        let mut out = vec![0xFF, marker];
+        out.extend(len.to_be_bytes());
+        out.extend(payload);
+        out
+    }
+
+    fn strip(input: &[u8]) -> io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        ExifStripper::new(input).read_to_end(&mut out)?;
+        Ok(out)
+    }
+
+    #[test]
+    fn removes_exif_and_keeps_everything_else() {
+        let jfif = segment(0xE0, b"JFIF\0\x01\x01");
+        let xmp = segment(APP1, b"http://ns.adobe.com/xap/1.0/\0<x/>");
+        let dqt = segment(0xDB, &[0; 65]);
+        let scan = [0xFF, SOS, 0x00, 0x02, 0x12, 0x34, 0xFF, 0x00, 0xFF, EOI];
+
+        let mut input = vec![0xFF, SOI];
+        input.extend(&jfif);
+        input.extend(segment(APP1, b"Exif\0\0GPS 35.6N 139.7E"));
+        input.extend(&xmp);
+        input.extend(&dqt);
+        input.extend(scan);
+
+        let mut expected = vec![0xFF, SOI];
+        expected.extend(jfif);
+        expected.extend(xmp);
+        expected.extend(dqt);
+        expected.extend(scan);
+        assert_eq!(strip(&input).unwrap(), expected);
+    }
+
+    #[test]
+    fn rejects_non_jpeg() {
+        let err = strip(b"\x89PNG\r\n").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn truncated_header_is_an_error() {
+        let mut input = vec![0xFF, SOI];
+        input.extend(&segment(0xE0, b"JFIF")[..4]);
+        assert!(strip(&input).is_err());
+    }
+
+    #[rstest]
+    #[case("photo.jpg", true)]
+    #[case("IMG_0001.JPEG", true)]
+    #[case("scan.jpe", true)]
+    #[case("diagram.png", false)]
+    #[case("jpg", false)]
+    fn jpeg_names(#[case] name: &str, #[case] expected: bool) {
+        assert_eq!(is_jpeg_name(name), expected);
+    }
+}
@@ -0,0 +1,254 @@
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// What happened to the message a record refers to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    #[default]
+    Send,
+    Edit,
+    Delete,
+}
+
+/// One successful send (or later edit/delete of it), as recorded in the local history file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SendRecord {
+    /// RFC 3339 time of the send (UTC)
+    pub time: String,
+    #[serde(default)]
+    pub action: Action,
+    /// Profile used (`None` = default)
+    pub profile: Option<String>,
+    /// Conversation ID when Slack returned one, otherwise the configured channel
+    pub channel: String,
+    /// Message ts (not available for file uploads)
+    pub ts: Option<String>,
+    /// SHA-256 of the message text; the text itself is never stored
+    pub text_sha256: Option<String>,
+    pub file: Option<String>,
+}
+
+impl SendRecord {
+    pub fn new(
+        profile: Option<&str>,
+        channel: &str,
+        ts: Option<&str>,
+        text: Option<&str>,
+        file: Option<&str>,
+    ) -> Self {
+        Self {
+            time: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            action: Action::Send,
+            profile: profile.map(String::from),
+            channel: channel.to_string(),
+            ts: ts.map(String::from),
+            text_sha256: text.filter(|t| !t.is_empty()).map(text_hash),
+            file: file.map(String::from),
+        }
+    }
+
+    pub fn with_action(self, action: Action) -> Self {
+        Self { action, ..self }
+    }
+}
+
+fn text_hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+pub fn history_path() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir().context("could not determine data directory")?;
+    Ok(data_dir.join("slafling").join("history.jsonl"))
+}
+
+/// Append a record as one JSON line, creating the file (mode 0600 on Unix) if needed.
+pub fn append(path: &Path, record: &SendRecord) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+
+    let mut line = serde_json::to_string(record)?;
+    line.push('\n');
+    file.write_all(line.as_bytes())
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Read all records, oldest first. A missing file is an empty history.
+pub fn read_all(path: &Path) -> Result<Vec<SendRecord>> {
+    let file = match std::fs::File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("failed to open {}", path.display())),
+    };
+
+    let mut records = Vec::new();
+    for (i, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let line = line.with_context(|| format!("failed to read {}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(&line)
+            .with_context(|| format!("{}:{}: invalid history record", path.display(), i + 1))?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// The most recent message sent from `profile` that has a ts and has not been deleted since.
+pub fn last_message<'a>(
+    records: &'a [SendRecord],
+    profile: Option<&str>,
+) -> Option<&'a SendRecord> {
+    let mut deleted = Vec::new();
+    for record in records.iter().rev() {
+        let Some(ts) = record.ts.as_deref() else {
+            continue;
+        };
+        if record.profile.as_deref() != profile {
+            continue;
+        }
+        match record.action {
+            Action::Delete => deleted.push((record.channel.as_str(), ts)),
+            Action::Edit => {}
+            Action::Send => {
+                if !deleted.contains(&(record.channel.as_str(), ts)) {
+                    return Some(record);
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_hashes_text_instead_of_storing_it() {
+        let record = SendRecord::new(Some("work"), "C01", Some("1.2"), Some("hello"), None);
+        assert_eq!(
+            record.text_sha256.as_deref(),
+            Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+        );
+        assert!(!serde_json::to_string(&record).unwrap().contains("hello"));
+    }
+
+    #[test]
+    fn new_skips_hash_for_empty_text() {
+        let record = SendRecord::new(None, "#general", None, Some(""), Some("log.txt"));
+        assert!(record.text_sha256.is_none());
+        assert_eq!(record.file.as_deref(), Some("log.txt"));
+    }
+
+    #[test]
+    fn append_writes_one_json_line_per_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("history.jsonl");
+        let first = SendRecord::new(None, "C01", Some("1.1"), Some("a"), None);
+        let second = SendRecord::new(Some("work"), "C02", Some("2.2"), Some("b"), None);
+        append(&path, &first).unwrap();
+        append(&path, &second).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let records: Vec<SendRecord> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(records, vec![first, second]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn append_creates_private_file() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        append(&path, &SendRecord::new(None, "C01", None, None, None)).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    #[test]
+    fn read_all_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_all(&dir.path().join("none.jsonl")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn read_all_reports_bad_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        std::fs::write(&path, "not json\n").unwrap();
+        let err = read_all(&path).unwrap_err();
+        assert!(format!("{err:#}").contains("history.jsonl:1"));
+    }
+
+    #[test]
+    fn read_all_defaults_missing_action_to_send() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        std::fs::write(
+            &path,
+            r#"{"time":"t","profile":null,"channel":"C01","ts":"1.1","text_sha256":null,"file":null}"#,
+        )
+        .unwrap();
+        assert_eq!(read_all(&path).unwrap()[0].action, Action::Send);
+    }
+
+    fn sent(profile: Option<&str>, ts: Option<&str>) -> SendRecord {
+        SendRecord::new(profile, "C01", ts, Some("x"), None)
+    }
+
+    #[test]
+    fn last_message_picks_latest_for_profile() {
+        let records = vec![
+            sent(None, Some("1.1")),
+            sent(Some("work"), Some("2.2")),
+            sent(None, Some("3.3")),
+            sent(None, None), // file upload, no ts
+        ];
+        assert_eq!(
+            last_message(&records, None).unwrap().ts.as_deref(),
+            Some("3.3")
+        );
+        assert_eq!(
+            last_message(&records, Some("work")).unwrap().ts.as_deref(),
+            Some("2.2")
+        );
+        assert!(last_message(&records, Some("other")).is_none());
+    }
+
+    #[test]
+    fn last_message_skips_deleted_and_ignores_edits() {
+        let records = vec![
+            sent(None, Some("1.1")),
+            sent(None, Some("2.2")),
+            sent(None, Some("2.2")).with_action(Action::Edit),
+            sent(None, Some("2.2")).with_action(Action::Delete),
+        ];
+        assert_eq!(
+            last_message(&records, None).unwrap().ts.as_deref(),
+            Some("1.1")
+        );
+    }
+}
@@ -1,4 +1,8 @@
 use anyhow::Result;
+use secrecy::SecretString;
+
+#[cfg(target_os = "macos")]
+use secrecy::ExposeSecret;
 
 #[cfg(target_os = "macos")]
 const SERVICE: &str = "slafling";
@@ -9,19 +13,19 @@ fn account_name(profile: Option<&str>) -> &str {
 }
 
 #[cfg(target_os = "macos")]
-pub fn get_token(profile: Option<&str>) -> Result<Option<String>> {
+pub fn get_token(profile: Option<&str>) -> Result<Option<SecretString>> {
     let entry = keyring::Entry::new(SERVICE, account_name(profile))?;
     match entry.get_password() {
-        Ok(token) => Ok(Some(token)),
+        Ok(token) => Ok(Some(token.into())),
         Err(keyring::Error::NoEntry) => Ok(None),
         Err(e) => Err(e.into()),
     }
 }
 
 #[cfg(target_os = "macos")]
-pub fn set_token(profile: Option<&str>, token: &str) -> Result<()> {
+pub fn set_token(profile: Option<&str>, token: &SecretString) -> Result<()> {
     let entry = keyring::Entry::new(SERVICE, account_name(profile))?;
-    entry.set_password(token)?;
+    entry.set_password(token.expose_secret())?;
     Ok(())
 }
 
@@ -36,12 +40,12 @@ pub fn delete_token(profile: Option<&str>) -> Result<()> {
 }
 
 #[cfg(not(target_os = "macos"))]
-pub fn get_token(_profile: Option<&str>) -> Result<Option<String>> {
+pub fn get_token(_profile: Option<&str>) -> Result<Option<SecretString>> {
     Ok(None)
 }
 
 #[cfg(not(target_os = "macos"))]
-pub fn set_token(_profile: Option<&str>, _token: &str) -> Result<()> {
+pub fn set_token(_profile: Option<&str>, _token: &SecretString) -> Result<()> {
     anyhow::bail!("Keychain is only supported on macOS")
 }
 
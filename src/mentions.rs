@@ -0,0 +1,195 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+
+use crate::slack::{self, Member};
+
+/// How long a cached member list is trusted before users.list is called again.
+const CACHE_TTL_SECS: i64 = 24 * 60 * 60;
+
+const BROADCASTS: [&str; 3] = ["here", "channel", "everyone"];
+
+#[derive(Serialize, Deserialize)]
+struct Cache {
+    /// Unix time the member list was fetched
+    fetched_at: i64,
+    members: Vec<Member>,
+}
+
+/// Per-workspace cache file, keyed by the token's fingerprint like the identity cache.
+pub fn cache_path(token: &SecretString) -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir().context("could not determine cache directory")?;
+    Ok(cache_dir
+        .join("slafling")
+        .join("users")
+        .join(slack::fingerprint(token)))
+}
+
+fn read_cache(path: &Path, now: i64) -> Option<Vec<Member>> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let cache: Cache = serde_json::from_str(&content).ok()?;
+    (now - cache.fetched_at < CACHE_TTL_SECS).then_some(cache.members)
+}
+
+fn write_cache(path: &Path, members: &[Member], now: i64) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+    let cache = Cache {
+        fetched_at: now,
+        members: members.to_vec(),
+    };
+    std::fs::write(path, serde_json::to_string(&cache)?)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+fn is_handle_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')
+}
+
+/// Byte ranges of the `@handle` mentions in `text`, `@` included.
+/// Email addresses, existing `<@U…>` markup and broadcasts are left alone.
+fn find_handles(text: &str) -> Vec<(usize, usize)> {
+    let mut found = Vec::new();
+    let mut prev = None;
+    for (i, c) in text.char_indices() {
+        if c == '@' && !prev.is_some_and(|p: char| is_handle_char(p) || p == '<') {
+            let rest = &text[i + 1..];
+            let len = rest.find(|c| !is_handle_char(c)).unwrap_or(rest.len());
+            // A trailing dot ends the sentence, not the handle
+            let handle = rest[..len].trim_end_matches('.');
+            if !handle.is_empty() && !BROADCASTS.contains(&handle) {
+                found.push((i, i + 1 + handle.len()));
+            }
+        }
+        prev = Some(c);
+    }
+    found
+}
+
+fn lookup<'a>(members: &'a [Member], handle: &str) -> Option<&'a str> {
+    members
+        .iter()
+        .find(|m| {
+            m.name.eq_ignore_ascii_case(handle) || m.display_name.eq_ignore_ascii_case(handle)
+        })
+        .map(|m| m.id.as_str())
+}
+
+/// Replace each known `@handle` with `<@U…>`; returns the new text and the handles not found.
+fn rewrite<'t>(text: &'t str, members: &[Member]) -> (String, Vec<&'t str>) {
+    let mut out = String::with_capacity(text.len());
+    let mut missing = Vec::new();
+    let mut last = 0;
+    for (start, end) in find_handles(text) {
+        let handle = &text[start + 1..end];
+        out.push_str(&text[last..start]);
+        match lookup(members, handle) {
+            Some(id) => out.push_str(&format!("<@{id}>")),
+            None => {
+                out.push_str(&text[start..end]);
+                missing.push(handle);
+            }
+        }
+        last = end;
+    }
+    out.push_str(&text[last..]);
+    (out, missing)
+}
+
+fn fetch_members(token: &SecretString, path: &Path, now: i64) -> Result<Vec<Member>> {
+    let members = slack::list_members(token)?;
+    if let Err(e) = write_cache(path, &members, now) {
+        crate::redact::eprintln_redacted!("warning: {e:#}");
+    }
+    Ok(members)
+}
+
+/// Turn `@alice` in `text` into a real mention, looking users up via a cached users.list.
+/// A stale or incomplete cache is refreshed once; handles that still don't match stay as plain text.
+pub fn resolve(token: &SecretString, text: &str) -> Result<String> {
+    let handles: Vec<&str> = find_handles(text)
+        .into_iter()
+        .map(|(start, end)| &text[start + 1..end])
+        .collect();
+    if handles.is_empty() {
+        return Ok(text.to_string());
+    }
+
+    let path = cache_path(token)?;
+    let now = chrono::Utc::now().timestamp();
+    let members = match read_cache(&path, now) {
+        Some(cached) if handles.iter().all(|h| lookup(&cached, h).is_some()) => cached,
+        _ => fetch_members(token, &path, now)?,
+    };
+
+    let (out, missing) = rewrite(text, &members);
+    for handle in missing {
+        crate::redact::eprintln_redacted!(
+            "warning: @{handle} is not a known user; sent as plain text"
+        );
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn members() -> Vec<Member> {
+        vec![
+            Member {
+                id: "U1".to_string(),
+                name: "alice".to_string(),
+                display_name: "Alice S".to_string(),
+            },
+            Member {
+                id: "U2".to_string(),
+                name: "bob.k".to_string(),
+                display_name: "bob".to_string(),
+            },
+        ]
+    }
+
+    #[rstest]
+    #[case("hi @alice", "hi <@U1>")]
+    #[case("@alice, @Bob.", "<@U1>, <@U2>.")]
+    #[case("cc @bob.k", "cc <@U2>")]
+    #[case("(@alice)", "(<@U1>)")]
+    #[case("mail alice@example.com", "mail alice@example.com")]
+    #[case("<@U9> stays", "<@U9> stays")]
+    #[case("@here @\u{200B}channel", "@here @\u{200B}channel")]
+    #[case("lone @ sign", "lone @ sign")]
+    fn rewrite_cases(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(rewrite(input, &members()).0, expected);
+    }
+
+    #[test]
+    fn rewrite_reports_unknown_handles() {
+        let (out, missing) = rewrite("@carol and @alice", &members());
+        assert_eq!(out, "@carol and <@U1>");
+        assert_eq!(missing, vec!["carol"]);
+    }
+
+    #[test]
+    fn cache_round_trip_and_expiry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("slafling").join("users.json");
+        assert!(read_cache(&path, 1000).is_none());
+
+        write_cache(&path, &members(), 1000).unwrap();
+        assert_eq!(read_cache(&path, 1000 + 60).unwrap(), members());
+        assert!(read_cache(&path, 1000 + CACHE_TTL_SECS).is_none());
+    }
+
+    #[test]
+    fn cache_path_does_not_contain_token() {
+        let path = cache_path(&"xoxb-secret-1".into()).unwrap();
+        assert!(!path.to_string_lossy().contains("xoxb"));
+    }
+}
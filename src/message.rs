@@ -0,0 +1,506 @@
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+
+use crate::cli::Level;
+use crate::config::LevelTemplate;
+
+/// Prefix a message with an outcome emoji and an "exited N" note for a command's exit code.
+pub fn annotate_status(code: i32, text: &str) -> String {
+    let (emoji, note) = match code {
+        0 => ("✅", "exited 0".to_string()),
+        129..=255 => ("⚠️", format!("exited {code}, signal {}", code - 128)),
+        _ => ("❌", format!("exited {code}")),
+    };
+    if text.is_empty() {
+        format!("{emoji} {note}")
+    } else {
+        format!("{emoji} {text} ({note})")
+    }
+}
+
+const BROADCASTS: [&str; 3] = ["here", "channel", "everyone"];
+
+/// Defuse channel-wide mentions so a stray `@here` or `<!channel>` in piped text pages nobody.
+/// A zero-width space after the `@` keeps the text readable but unparsed.
+pub fn neutralize_broadcasts(text: &str) -> String {
+    let mut out = text.to_string();
+    for name in BROADCASTS {
+        // <!here>, <!here|label>
+        let open = format!("<!{name}");
+        while let Some(start) = out.find(&open) {
+            let rest = &out[start + open.len()..];
+            if !(rest.starts_with('>') || rest.starts_with('|')) {
+                break;
+            }
+            let Some(end) = rest.find('>') else { break };
+            out.replace_range(
+                start..start + open.len() + end + 1,
+                &format!("@\u{200B}{name}"),
+            );
+        }
+        out = out.replace(&format!("@{name}"), &format!("@\u{200B}{name}"));
+    }
+    out
+}
+
+/// Escape the characters Slack treats as control sequences (`<@U…>`, `<!here>`, links).
+pub fn escape_control_chars(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Above this, a code block is better sent as a snippet (Slack truncates long messages).
+pub const CODE_BLOCK_MAX_CHARS: usize = 4000;
+
+/// Slack truncates message text beyond this many characters.
+pub const MESSAGE_MAX_CHARS: usize = 4000;
+
+/// Room kept free when cutting an overlong line, for the fence lines around it.
+const SPLIT_MARGIN_CHARS: usize = 64;
+
+fn is_fence(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
+
+/// Split text into chunks of at most `max` characters, at line boundaries where possible.
+/// A code block cut by a chunk boundary is closed at the end of one chunk and reopened
+/// (with its language) at the start of the next, so every chunk renders on its own.
+pub fn split_long_text(text: &str, max: usize) -> Vec<String> {
+    let piece_max = max - SPLIT_MARGIN_CHARS.min(max / 4);
+    let mut chunks = Vec::new();
+    let mut cur = String::new();
+    let mut cur_len = 0;
+    let mut open_fence: Option<String> = None;
+
+    for line in text.split('\n') {
+        let chars: Vec<char> = line.chars().collect();
+        let pieces: Vec<String> = if chars.is_empty() {
+            vec![String::new()]
+        } else {
+            chars
+                .chunks(piece_max)
+                .map(|c| c.iter().collect())
+                .collect()
+        };
+        for piece in pieces {
+            let piece_len = piece.chars().count();
+            let close_len = if open_fence.is_some() { 4 } else { 0 };
+            if !cur.is_empty() && cur_len + 1 + piece_len + close_len > max {
+                if open_fence.is_some() {
+                    cur.push_str("\n```");
+                }
+                chunks.push(std::mem::take(&mut cur));
+                cur_len = 0;
+                if let Some(fence) = &open_fence {
+                    cur.push_str(fence);
+                    cur_len = fence.chars().count();
+                }
+            }
+            if !cur.is_empty() {
+                cur.push('\n');
+                cur_len += 1;
+            }
+            if is_fence(&piece) {
+                open_fence = match open_fence {
+                    Some(_) => None,
+                    None => Some(piece.trim().to_string()),
+                };
+            }
+            cur.push_str(&piece);
+            cur_len += piece_len;
+        }
+    }
+    if !cur.is_empty() {
+        chunks.push(cur);
+    }
+    chunks
+}
+
+/// Which part of the text `truncate` keeps.
+#[derive(Clone, Copy)]
+pub enum Keep {
+    Start,
+    End,
+}
+
+/// Cut text to at most `max` characters, noting how much was dropped on its own line.
+pub fn truncate(text: &str, max: usize, keep: Keep) -> String {
+    let len = text.chars().count();
+    if len <= max {
+        return text.to_string();
+    }
+    // The note's length depends on the dropped count, which depends on the note: size it for the worst case
+    let note_len = format!("… ({len} characters truncated)\n").chars().count();
+    let kept_len = max.saturating_sub(note_len);
+    let dropped = len - kept_len;
+    match keep {
+        Keep::Start => {
+            let kept: String = text.chars().take(kept_len).collect();
+            format!("{kept}\n… ({dropped} characters truncated)")
+        }
+        Keep::End => {
+            let kept: String = text.chars().skip(dropped).collect();
+            format!("… ({dropped} characters truncated)\n{kept}")
+        }
+    }
+}
+
+/// Length of the first-line excerpt in `long_text_summary`.
+const SUMMARY_MAX_CHARS: usize = 200;
+
+/// One-line comment for overlong text uploaded as a file: its first non-empty line, shortened.
+pub fn long_text_summary(text: &str) -> String {
+    let first = text
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or_default();
+    let excerpt = if first.chars().count() > SUMMARY_MAX_CHARS {
+        let cut: String = first.chars().take(SUMMARY_MAX_CHARS).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        first.to_string()
+    };
+    format!(
+        "{excerpt} (full text attached, {} characters)",
+        text.chars().count()
+    )
+}
+
+/// Wrap text in a fenced code block, breaking up embedded ``` so they can't close it early.
+pub fn code_block(text: &str) -> String {
+    let escaped = text.replace("```", "`\u{200B}`\u{200B}`");
+    format!("```\n{}\n```", escaped.trim_end_matches('\n'))
+}
+
+/// Append `sha256sum`-style lines (`<hex>  <name>`) in a code block, so they can be pasted into `sha256sum -c`.
+pub fn append_checksums(text: &str, sums: &[(String, String)]) -> String {
+    let lines = sums
+        .iter()
+        .map(|(name, hex)| format!("{hex}  {name}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let block = code_block(&lines);
+    if text.is_empty() {
+        block
+    } else {
+        format!("{text}\n{block}")
+    }
+}
+
+/// Filename for a code snippet upload, with an extension derived from the language hint.
+pub fn snippet_filename(lang: &str) -> String {
+    let lang = lang.trim().to_ascii_lowercase();
+    let ext = match lang.as_str() {
+        "" | "text" | "plain" => "txt",
+        "rust" => "rs",
+        "python" => "py",
+        "javascript" => "js",
+        "typescript" => "ts",
+        "ruby" => "rb",
+        "shell" | "bash" | "zsh" => "sh",
+        "yaml" => "yml",
+        "markdown" => "md",
+        other => other,
+    };
+    format!("snippet.{ext}")
+}
+
+/// Validate an attachment color: `good`, `warning`, `danger`, or a hex code like `#36a64f`.
+pub fn parse_color(color: &str) -> Result<String> {
+    let color = color.trim();
+    if matches!(color, "good" | "warning" | "danger") {
+        return Ok(color.to_string());
+    }
+    let hex = color.strip_prefix('#').unwrap_or(color);
+    if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        bail!(
+            "invalid color '{color}' (expected good, warning, danger, or a hex code like #36a64f)"
+        );
+    }
+    Ok(format!("#{}", hex.to_ascii_lowercase()))
+}
+
+/// Wrap text in a legacy attachment so it gets a colored sidebar.
+pub fn color_attachment(color: &str, text: &str) -> Value {
+    json!({
+        "color": color,
+        "text": text,
+        "fallback": text,
+        "mrkdwn_in": ["text"],
+    })
+}
+
+/// Formatting for a `--level` message once the config's template is layered over the built-ins.
+#[derive(Debug, PartialEq)]
+pub struct LevelStyle {
+    pub emoji: Option<String>,
+    pub color: Option<String>,
+    /// Ready-to-send markup such as `<!here>` or `<@U012AB3CD>`.
+    pub mention: Option<String>,
+}
+
+impl LevelStyle {
+    /// Prefix the text with the mention and emoji.
+    pub fn decorate(&self, text: &str) -> String {
+        [
+            self.mention.as_deref(),
+            self.emoji.as_deref(),
+            Some(text).filter(|t| !t.is_empty()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
+pub fn level_style(level: Level, template: Option<&LevelTemplate>) -> Result<LevelStyle> {
+    let (emoji, color) = match level {
+        Level::Info => ("ℹ️", "#439fe0"),
+        Level::Warn => ("⚠️", "warning"),
+        Level::Error => ("❌", "danger"),
+        Level::Critical => ("🚨", "danger"),
+    };
+    let mut merged = LevelTemplate {
+        emoji: Some(emoji.to_string()),
+        color: Some(color.to_string()),
+        mention: None,
+    };
+    if let Some(template) = template {
+        merged.merge(template);
+    }
+    let non_empty = |v: Option<String>| v.filter(|s| !s.is_empty());
+    Ok(LevelStyle {
+        emoji: non_empty(merged.emoji),
+        color: non_empty(merged.color)
+            .map(|c| parse_color(&c))
+            .transpose()?,
+        mention: non_empty(merged.mention)
+            .map(|m| mention_markup(&m))
+            .transpose()?,
+    })
+}
+
+/// Slack markup for a configured mention: here/channel/everyone, a user ID, or a user group ID.
+pub fn mention_markup(who: &str) -> Result<String> {
+    let who = who.trim();
+    let is_id = |prefixes: &[char]| {
+        who.len() > 1
+            && who.starts_with(prefixes)
+            && who
+                .bytes()
+                .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+    };
+    if BROADCASTS.contains(&who) {
+        Ok(format!("<!{who}>"))
+    } else if is_id(&['U', 'W']) {
+        Ok(format!("<@{who}>"))
+    } else if is_id(&['S']) {
+        Ok(format!("<!subteam^{who}>"))
+    } else {
+        bail!("invalid mention '{who}' (expected here, channel, everyone, a user ID like U012AB3CD, or a user group ID like S012AB3CD)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(0, "backup finished", "✅ backup finished (exited 0)")]
+    #[case(1, "backup finished", "❌ backup finished (exited 1)")]
+    #[case(127, "deploy", "❌ deploy (exited 127)")]
+    #[case(130, "deploy", "⚠️ deploy (exited 130, signal 2)")]
+    #[case(-1, "x", "❌ x (exited -1)")]
+    #[case(0, "", "✅ exited 0")]
+    #[case(2, "", "❌ exited 2")]
+    fn annotate_status_cases(#[case] code: i32, #[case] text: &str, #[case] expected: &str) {
+        assert_eq!(annotate_status(code, text), expected);
+    }
+
+    #[rstest]
+    #[case("deploy done @here", "deploy done @\u{200B}here")]
+    #[case("<!channel> heads up", "@\u{200B}channel heads up")]
+    #[case("<!here|here> and <!everyone>", "@\u{200B}here and @\u{200B}everyone")]
+    #[case("<!subteam^S123> stays", "<!subteam^S123> stays")]
+    #[case("<!hereafter>", "<!hereafter>")]
+    #[case("mail me@example.com", "mail me@example.com")]
+    fn neutralize_broadcasts_cases(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(neutralize_broadcasts(input), expected);
+    }
+
+    #[test]
+    fn escape_control_chars_cases() {
+        assert_eq!(
+            escape_control_chars("a < b && <!here> > c"),
+            "a &lt; b &amp;&amp; &lt;!here&gt; &gt; c"
+        );
+        assert_eq!(escape_control_chars("*bold* _it_"), "*bold* _it_");
+    }
+
+    #[test]
+    fn code_block_fences_text() {
+        assert_eq!(code_block("let x = 1;\n"), "```\nlet x = 1;\n```");
+    }
+
+    #[test]
+    fn code_block_breaks_embedded_fences() {
+        let block = code_block("a\n```\nb");
+        assert_eq!(block.matches("```").count(), 2);
+        assert!(block.contains("`\u{200B}`\u{200B}`"));
+    }
+
+    #[rstest]
+    #[case("", "snippet.txt")]
+    #[case("rust", "snippet.rs")]
+    #[case("Python", "snippet.py")]
+    #[case("bash", "snippet.sh")]
+    #[case("go", "snippet.go")]
+    fn snippet_filename_cases(#[case] lang: &str, #[case] expected: &str) {
+        assert_eq!(snippet_filename(lang), expected);
+    }
+
+    #[test]
+    fn truncate_keeps_start_or_end() {
+        let text = "0123456789".repeat(10);
+        let start = truncate(&text, 50, Keep::Start);
+        assert!(start.chars().count() <= 50, "{start}");
+        assert!(start.starts_with("0123"));
+        assert!(start.ends_with("characters truncated)"));
+
+        let end = truncate(&text, 50, Keep::End);
+        assert!(end.chars().count() <= 50, "{end}");
+        assert!(end.starts_with("… ("));
+        assert!(end.ends_with("6789"));
+
+        assert_eq!(truncate("short", 50, Keep::End), "short");
+    }
+
+    #[test]
+    fn split_long_text_at_lines() {
+        let text = "aaaa\nbbbb\ncccc";
+        assert_eq!(split_long_text(text, 9), vec!["aaaa\nbbbb", "cccc"]);
+        assert_eq!(split_long_text(text, 100), vec![text]);
+    }
+
+    #[test]
+    fn split_long_text_reopens_code_fence() {
+        let text = "intro\n```rust\nlet a = 1;\nlet b = 2;\n```\ndone";
+        let chunks = split_long_text(text, 30);
+        assert_eq!(
+            chunks,
+            vec![
+                "intro\n```rust\nlet a = 1;\n```",
+                "```rust\nlet b = 2;\n```\ndone",
+            ]
+        );
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= 30, "{chunk:?}");
+            assert_eq!(chunk.lines().filter(|l| is_fence(l)).count() % 2, 0);
+        }
+    }
+
+    #[test]
+    fn split_long_text_cuts_overlong_lines() {
+        let text = "x".repeat(250);
+        let chunks = split_long_text(&text, 100);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 100));
+        assert_eq!(chunks.concat().replace('\n', ""), text);
+    }
+
+    #[test]
+    fn long_text_summary_uses_first_line() {
+        assert_eq!(
+            long_text_summary("\n  Build failed  \nline 2\nline 3"),
+            "Build failed (full text attached, 31 characters)"
+        );
+        let long = "x".repeat(300);
+        let summary = long_text_summary(&long);
+        assert!(summary.starts_with(&format!("{}…", "x".repeat(200))));
+        assert!(summary.ends_with("(full text attached, 300 characters)"));
+    }
+
+    #[test]
+    fn append_checksums_after_text() {
+        let sums = vec![
+            ("a.log".to_string(), "aa11".to_string()),
+            ("b.log".to_string(), "bb22".to_string()),
+        ];
+        assert_eq!(
+            append_checksums("build 42", &sums),
+            "build 42\n```\naa11  a.log\nbb22  b.log\n```"
+        );
+        assert_eq!(append_checksums("", &sums[..1]), "```\naa11  a.log\n```");
+    }
+
+    #[rstest]
+    #[case("good", "good")]
+    #[case("danger", "danger")]
+    #[case("#36A64F", "#36a64f")]
+    #[case("36a64f", "#36a64f")]
+    fn parse_color_valid(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(parse_color(input).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("red")]
+    #[case("#fff")]
+    #[case("#gggggg")]
+    #[case("")]
+    fn parse_color_invalid(#[case] input: &str) {
+        assert!(parse_color(input).is_err());
+    }
+
+    #[rstest]
+    #[case("here", "<!here>")]
+    #[case("channel", "<!channel>")]
+    #[case("U012AB3CD", "<@U012AB3CD>")]
+    #[case("W012AB3CD", "<@W012AB3CD>")]
+    #[case("S012AB3CD", "<!subteam^S012AB3CD>")]
+    fn mention_markup_valid(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(mention_markup(input).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("@alice")]
+    #[case("u012ab3cd")]
+    #[case("U")]
+    #[case("oncall")]
+    fn mention_markup_invalid(#[case] input: &str) {
+        assert!(mention_markup(input).is_err());
+    }
+
+    #[test]
+    fn level_style_builtin() {
+        let style = level_style(Level::Critical, None).unwrap();
+        assert_eq!(style.emoji.as_deref(), Some("🚨"));
+        assert_eq!(style.color.as_deref(), Some("danger"));
+        assert!(style.mention.is_none());
+        assert_eq!(style.decorate("db down"), "🚨 db down");
+    }
+
+    #[test]
+    fn level_style_template_overrides_and_disables() {
+        let template = LevelTemplate {
+            emoji: Some(":fire:".to_string()),
+            color: Some(String::new()),
+            mention: Some("here".to_string()),
+        };
+        let style = level_style(Level::Error, Some(&template)).unwrap();
+        assert_eq!(style.color, None);
+        assert_eq!(style.decorate("db down"), "<!here> :fire: db down");
+        assert_eq!(style.decorate(""), "<!here> :fire:");
+    }
+
+    #[test]
+    fn color_attachment_shape() {
+        let a = color_attachment("danger", "disk full");
+        assert_eq!(a["color"], "danger");
+        assert_eq!(a["text"], "disk full");
+        assert_eq!(a["fallback"], "disk full");
+    }
+}
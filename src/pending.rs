@@ -0,0 +1,196 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// How often a held send checks whether `slafling cancel` removed its marker.
+const POLL: Duration = Duration::from_millis(100);
+
+/// Markers older than their deadline by this much belong to a process that was killed
+/// (e.g. by Ctrl-C) before it could clean up.
+const STALE_AFTER_SECS: i64 = 60;
+
+/// Marker for a send held back by `undo_delay`; deleting it cancels the send.
+#[derive(Serialize, Deserialize)]
+pub struct PendingSend {
+    /// Profile used (`None` = default); `cancel` only touches its own profile's sends
+    pub profile: Option<String>,
+    pub channel: String,
+    /// Unix time the send goes out
+    pub send_at: i64,
+}
+
+pub fn pending_dir() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir().context("could not determine data directory")?;
+    Ok(data_dir.join("slafling").join("pending"))
+}
+
+/// Removes the marker when the hold ends, however it ends.
+struct Marker(PathBuf);
+
+impl Drop for Marker {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Hold a send for `delay`, calling `on_tick` with the whole seconds left each time that
+/// changes. Fails with "cancelled" if `cancel` removes the marker first; at the deadline the
+/// hold removes it itself, so a `cancel` that counted the send always stops it.
+pub fn hold(
+    dir: &Path,
+    item: &PendingSend,
+    delay: Duration,
+    mut on_tick: impl FnMut(u64),
+) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("failed to create directory {}", dir.display()))?;
+    let path = dir.join(format!(
+        "{}-{}.json",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    std::fs::write(&path, serde_json::to_string(item)?)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    let marker = Marker(path);
+
+    let start = Instant::now();
+    let mut shown = None;
+    loop {
+        let elapsed = start.elapsed();
+        if elapsed >= delay {
+            return match std::fs::remove_file(&marker.0) {
+                Ok(()) => Ok(()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => bail!("cancelled"),
+                Err(e) => {
+                    Err(e).with_context(|| format!("failed to remove {}", marker.0.display()))
+                }
+            };
+        }
+        let left = (delay - elapsed).as_secs_f64().ceil() as u64;
+        if shown != Some(left) {
+            on_tick(left);
+            shown = Some(left);
+        }
+        if !marker.0.exists() {
+            bail!("cancelled");
+        }
+        std::thread::sleep(POLL.min(delay - elapsed));
+    }
+}
+
+/// Cancel the profile's held sends by removing their markers; returns how many there were.
+/// Markers left behind by killed processes are removed without being counted.
+pub fn cancel(dir: &Path, profile: Option<&str>, now: i64) -> Result<usize> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e).with_context(|| format!("failed to read {}", dir.display())),
+    };
+    let mut cancelled = 0;
+    for entry in entries {
+        let path = entry
+            .with_context(|| format!("failed to read {}", dir.display()))?
+            .path();
+        let Ok(json) = std::fs::read_to_string(&path) else {
+            // Finished and cleaned up since the directory was read
+            continue;
+        };
+        let item: Option<PendingSend> = serde_json::from_str(&json).ok();
+        let stale = item
+            .as_ref()
+            .is_none_or(|item| now - item.send_at > STALE_AFTER_SECS);
+        if !stale
+            && item
+                .as_ref()
+                .is_some_and(|i| i.profile.as_deref() != profile)
+        {
+            continue;
+        }
+        if std::fs::remove_file(&path).is_ok() && !stale {
+            cancelled += 1;
+        }
+    }
+    Ok(cancelled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(profile: Option<&str>, send_at: i64) -> PendingSend {
+        PendingSend {
+            profile: profile.map(String::from),
+            channel: "#deploy".to_string(),
+            send_at,
+        }
+    }
+
+    #[test]
+    fn hold_counts_down_and_cleans_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ticks = Vec::new();
+        hold(
+            dir.path(),
+            &item(None, 0),
+            Duration::from_millis(1500),
+            |left| ticks.push(left),
+        )
+        .unwrap();
+        assert_eq!(ticks, vec![2, 1]);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn cancel_during_the_last_sleep_still_aborts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_path_buf();
+        // Polls at 0, 100 and 200ms, then sleeps to the 300ms deadline
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(250));
+            cancel(&path, None, 0).unwrap()
+        });
+        let err = hold(
+            dir.path(),
+            &item(None, 0),
+            Duration::from_millis(300),
+            |_| {},
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "cancelled");
+        assert_eq!(canceller.join().unwrap(), 1);
+    }
+
+    #[test]
+    fn cancel_aborts_a_held_send() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_path_buf();
+        let now = chrono::Utc::now().timestamp();
+        let held = std::thread::spawn(move || {
+            hold(
+                &path,
+                &item(Some("ci"), now + 5),
+                Duration::from_secs(5),
+                |_| {},
+            )
+        });
+        while std::fs::read_dir(dir.path()).unwrap().count() == 0 {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(cancel(dir.path(), None, now).unwrap(), 0);
+        assert_eq!(cancel(dir.path(), Some("ci"), now).unwrap(), 1);
+        let err = held.join().unwrap().unwrap_err();
+        assert_eq!(err.to_string(), "cancelled");
+    }
+
+    #[test]
+    fn cancel_drops_stale_markers_without_counting_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let stale = serde_json::to_string(&item(Some("other"), 1000)).unwrap();
+        std::fs::write(dir.path().join("1-1.json"), stale).unwrap();
+        assert_eq!(cancel(dir.path(), Some("ci"), 1000 + 3600).unwrap(), 0);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+}
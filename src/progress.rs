@@ -0,0 +1,129 @@
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use crate::config::format_size;
+use crate::slack::UploadFile;
+
+/// Redraws are throttled to this interval; the final 100% line is always drawn.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+const BAR_WIDTH: usize = 20;
+
+/// A single-line upload progress indicator on stderr, summed over all files of the upload.
+pub struct UploadBar {
+    label: String,
+    totals: Vec<u64>,
+    sent: Vec<u64>,
+    started: Instant,
+    last_draw: Option<Instant>,
+}
+
+impl UploadBar {
+    pub fn new(files: &[UploadFile]) -> Self {
+        let label = match files {
+            [file] => file.name.clone(),
+            _ => format!("{} files", files.len()),
+        };
+        Self {
+            label,
+            totals: files.iter().map(|f| f.len).collect(),
+            sent: vec![0; files.len()],
+            started: Instant::now(),
+            last_draw: None,
+        }
+    }
+
+    /// Record that `sent` bytes of the file at `index` have gone out, redrawing if due.
+    pub fn update(&mut self, index: usize, sent: u64) {
+        self.sent[index] = sent;
+        let sent: u64 = self.sent.iter().sum();
+        let total: u64 = self.totals.iter().sum();
+
+        let now = Instant::now();
+        let done = sent >= total;
+        if !done && self.last_draw.is_some_and(|t| now - t < REDRAW_INTERVAL) {
+            return;
+        }
+        self.last_draw = Some(now);
+
+        let line = render(&self.label, sent, total, now - self.started);
+        let mut stderr = std::io::stderr().lock();
+        // \r + clear-line keeps the bar on one line as it redraws
+        let _ = write!(stderr, "\r\x1b[2K{line}");
+        if done {
+            let _ = writeln!(stderr);
+        }
+        let _ = stderr.flush();
+    }
+}
+
+fn render(label: &str, sent: u64, total: u64, elapsed: Duration) -> String {
+    let fraction = if total == 0 {
+        1.0
+    } else {
+        (sent as f64 / total as f64).min(1.0)
+    };
+    let filled = (fraction * BAR_WIDTH as f64).round() as usize;
+    let bar = format!("{}{}", "#".repeat(filled), "-".repeat(BAR_WIDTH - filled));
+
+    let eta = if sent >= total {
+        String::new()
+    } else if sent == 0 {
+        "  ETA --:--".to_string()
+    } else {
+        let remaining = elapsed.as_secs_f64() * (total - sent) as f64 / sent as f64;
+        let secs = remaining.round() as u64;
+        format!("  ETA {}:{:02}", secs / 60, secs % 60)
+    };
+
+    format!(
+        "{label} [{bar}] {} / {} {:>3.0}%{eta}",
+        format_size(sent),
+        format_size(total),
+        fraction * 100.0
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_in_progress() {
+        let line = render(
+            "big.tar",
+            25 * 1024 * 1024,
+            100 * 1024 * 1024,
+            Duration::from_secs(10),
+        );
+        assert_eq!(
+            line,
+            "big.tar [#####---------------] 25.0MB / 100.0MB  25%  ETA 0:30"
+        );
+    }
+
+    #[test]
+    fn render_done_has_no_eta() {
+        let line = render("a.log", 2048, 2048, Duration::from_secs(1));
+        assert_eq!(line, "a.log [####################] 2.0KB / 2.0KB 100%");
+    }
+
+    #[test]
+    fn bar_sums_files() {
+        let files = [
+            UploadFile::from_bytes("a".to_string(), vec![0; 10]),
+            UploadFile::from_bytes("b".to_string(), vec![0; 30]),
+        ];
+        let mut bar = UploadBar::new(&files);
+        assert_eq!(bar.label, "2 files");
+        bar.update(0, 10);
+        bar.update(1, 20);
+        assert_eq!(bar.sent, vec![10, 20]);
+        assert_eq!(bar.totals.iter().sum::<u64>(), 40);
+    }
+
+    #[test]
+    fn render_before_first_byte() {
+        let line = render("a.log", 0, 2048, Duration::ZERO);
+        assert!(line.ends_with("0B / 2.0KB   0%  ETA --:--"), "{line}");
+    }
+}
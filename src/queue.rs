@@ -0,0 +1,317 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::slack::{MessageOptions, UploadFile};
+
+const ITEM_FILE: &str = "item.json";
+const LOCK_FILE: &str = ".lock";
+
+/// A queued file: its upload metadata plus where the copied content lives in the item directory.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct QueuedFile {
+    pub name: String,
+    pub title: Option<String>,
+    pub snippet_type: Option<String>,
+    /// File name of the copy inside the item directory
+    pub stored: String,
+}
+
+/// A send that failed because Slack was unreachable, kept for `slafling flush`.
+/// Unlike the history file, this holds the message text itself until it is delivered.
+#[derive(Serialize, Deserialize)]
+pub struct QueuedSend {
+    /// RFC 3339 time the send was queued (UTC)
+    pub queued_at: String,
+    /// Profile used (`None` = default); `flush` only delivers its own profile's items
+    pub profile: Option<String>,
+    pub channel: String,
+    /// Message text, or the upload comment (empty = none)
+    pub text: String,
+    #[serde(default)]
+    pub options: MessageOptions,
+    #[serde(default)]
+    pub files: Vec<QueuedFile>,
+    /// Follow-up chunks of a split long message, posted in the first one's thread;
+    /// each is dropped from the entry once delivered
+    #[serde(default)]
+    pub replies: Vec<String>,
+    /// Set once the first message is delivered, so a retry only sends the rest
+    #[serde(default)]
+    pub delivered: Option<Delivered>,
+}
+
+/// Where a partly delivered split send's remaining `replies` go.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Delivered {
+    pub channel: String,
+    /// Thread the replies are posted in
+    pub thread_ts: String,
+}
+
+pub fn queue_dir() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir().context("could not determine data directory")?;
+    Ok(data_dir.join("slafling").join("queue"))
+}
+
+/// Sortable by time, so listing the directory gives send order.
+fn new_id() -> String {
+    static SEQ: AtomicU32 = AtomicU32::new(0);
+    let now = chrono::Utc::now();
+    format!(
+        "{}-{:09}-{:03}",
+        now.format("%Y%m%dT%H%M%S%6f"),
+        std::process::id(),
+        SEQ.fetch_add(1, Ordering::Relaxed) % 1000
+    )
+}
+
+fn create_private_dir(path: &Path) -> Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder
+        .create(path)
+        .with_context(|| format!("failed to create directory {}", path.display()))
+}
+
+/// Store `item` and copies of `files` as a new queue entry and return its ID.
+/// The entry is assembled under a temporary name and renamed into place, so a
+/// concurrent `flush` never sees a half-written one.
+pub fn enqueue(dir: &Path, mut item: QueuedSend, files: &[UploadFile]) -> Result<String> {
+    let id = new_id();
+    let staging = dir.join(format!(".{id}.tmp"));
+    create_private_dir(&staging)?;
+    let result = (|| {
+        item.files = files
+            .iter()
+            .enumerate()
+            .map(|(i, file)| {
+                let stored = format!("{i}-{}", file.name);
+                file.save(&staging.join(&stored))?;
+                Ok(QueuedFile {
+                    name: file.name.clone(),
+                    title: file.title.clone(),
+                    snippet_type: file.snippet_type.clone(),
+                    stored,
+                })
+            })
+            .collect::<Result<_>>()?;
+        let item_path = staging.join(ITEM_FILE);
+        std::fs::write(&item_path, serde_json::to_string_pretty(&item)?)
+            .with_context(|| format!("failed to write {}", item_path.display()))?;
+        std::fs::rename(&staging, dir.join(&id))
+            .with_context(|| format!("failed to move queue entry into {}", dir.display()))
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_dir_all(&staging);
+    }
+    result.map(|()| id)
+}
+
+/// All queued entries, oldest first. A missing queue directory is an empty queue.
+pub fn list(dir: &Path) -> Result<Vec<(String, QueuedSend)>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("failed to read {}", dir.display())),
+    };
+    let mut ids = Vec::new();
+    for entry in entries {
+        let entry = entry.with_context(|| format!("failed to read {}", dir.display()))?;
+        let id = entry.file_name().to_string_lossy().into_owned();
+        if !id.starts_with('.') && entry.path().join(ITEM_FILE).is_file() {
+            ids.push(id);
+        }
+    }
+    ids.sort();
+
+    let mut items = Vec::with_capacity(ids.len());
+    for id in ids {
+        let path = dir.join(&id).join(ITEM_FILE);
+        let json = match std::fs::read_to_string(&path) {
+            Ok(json) => json,
+            // Delivered or dropped by another process since the directory was read
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+        };
+        let item = serde_json::from_str(&json)
+            .with_context(|| format!("{}: invalid queue entry", path.display()))?;
+        items.push((id, item));
+    }
+    Ok(items)
+}
+
+/// Exclusive hold on the queue for delivering or dropping entries; released on drop.
+/// New entries don't need it, as `enqueue` only ever adds a finished directory.
+pub struct QueueLock {
+    _file: std::fs::File,
+}
+
+/// Take the queue lock, waiting while another slafling process (e.g. an overlapping cron
+/// `flush`) holds it.
+pub fn lock(dir: &Path) -> Result<QueueLock> {
+    create_private_dir(dir)?;
+    let path = dir.join(LOCK_FILE);
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    match file.try_lock() {
+        Ok(()) => {}
+        Err(std::fs::TryLockError::WouldBlock) => {
+            eprintln!("waiting for another slafling process to release the queue...");
+            file.lock()
+                .with_context(|| format!("failed to lock {}", path.display()))?;
+        }
+        Err(std::fs::TryLockError::Error(e)) => {
+            return Err(e).with_context(|| format!("failed to lock {}", path.display()));
+        }
+    }
+    Ok(QueueLock { _file: file })
+}
+
+/// The stored copies of an entry's files, ready to upload.
+pub fn files(dir: &Path, id: &str, item: &QueuedSend) -> Result<Vec<UploadFile>> {
+    item.files
+        .iter()
+        .map(|queued| {
+            let mut file =
+                UploadFile::from_path(queued.name.clone(), &dir.join(id).join(&queued.stored))?;
+            file.title = queued.title.clone();
+            file.snippet_type = queued.snippet_type.clone();
+            Ok(file)
+        })
+        .collect()
+}
+
+/// Rewrite an entry's item, e.g. after delivering part of it. Written to a temporary file
+/// and renamed over the old one, so a crash never leaves it half-written.
+pub fn update(dir: &Path, id: &str, item: &QueuedSend) -> Result<()> {
+    let path = dir.join(id).join(ITEM_FILE);
+    let staging = dir.join(id).join(format!(".{ITEM_FILE}.tmp"));
+    std::fs::write(&staging, serde_json::to_string_pretty(item)?)
+        .with_context(|| format!("failed to write {}", staging.display()))?;
+    std::fs::rename(&staging, &path).with_context(|| format!("failed to update {}", path.display()))
+}
+
+/// Delete an entry and its stored files.
+pub fn remove(dir: &Path, id: &str) -> Result<()> {
+    let path = dir.join(id);
+    std::fs::remove_dir_all(&path).with_context(|| format!("failed to remove {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str) -> QueuedSend {
+        QueuedSend {
+            queued_at: "2026-10-16T09:00:00Z".to_string(),
+            profile: Some("ci".to_string()),
+            channel: "#deploy".to_string(),
+            text: text.to_string(),
+            options: MessageOptions {
+                thread_ts: Some("1712345678.123456".to_string()),
+                ..MessageOptions::default()
+            },
+            files: Vec::new(),
+            replies: Vec::new(),
+            delivered: None,
+        }
+    }
+
+    #[test]
+    fn enqueue_list_remove_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = dir.path().join("queue");
+        assert!(list(&queue).unwrap().is_empty());
+
+        let first = enqueue(&queue, item("first"), &[]).unwrap();
+        let second = enqueue(&queue, item("second"), &[]).unwrap();
+        let listed = list(&queue).unwrap();
+        assert_eq!(
+            listed.iter().map(|(id, _)| id.as_str()).collect::<Vec<_>>(),
+            vec![first.as_str(), second.as_str()]
+        );
+        assert_eq!(listed[0].1.text, "first");
+        assert_eq!(
+            listed[0].1.options.thread_ts.as_deref(),
+            Some("1712345678.123456")
+        );
+
+        remove(&queue, &first).unwrap();
+        let listed = list(&queue).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].0, second);
+    }
+
+    #[test]
+    fn enqueue_keeps_file_copies() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = dir.path().join("queue");
+        let mut upload = UploadFile::from_bytes("build.log".to_string(), b"ok\n".to_vec());
+        upload.title = Some("Nightly build".to_string());
+
+        let id = enqueue(&queue, item("logs"), &[upload]).unwrap();
+        let (_, queued) = list(&queue).unwrap().remove(0);
+        assert_eq!(queued.files[0].name, "build.log");
+
+        let restored = files(&queue, &id, &queued).unwrap();
+        assert_eq!(restored[0].len, 3);
+        assert_eq!(restored[0].title.as_deref(), Some("Nightly build"));
+        assert_eq!(
+            std::fs::read(queue.join(&id).join(&queued.files[0].stored)).unwrap(),
+            b"ok\n"
+        );
+    }
+
+    #[test]
+    fn update_records_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut split = item("part 1");
+        split.replies = vec!["part 2".to_string(), "part 3".to_string()];
+        let id = enqueue(dir.path(), split, &[]).unwrap();
+
+        let (_, mut queued) = list(dir.path()).unwrap().remove(0);
+        queued.replies.remove(0);
+        queued.delivered = Some(Delivered {
+            channel: "C0123".to_string(),
+            thread_ts: "1712345678.123456".to_string(),
+        });
+        update(dir.path(), &id, &queued).unwrap();
+
+        let (_, queued) = list(dir.path()).unwrap().remove(0);
+        assert_eq!(queued.replies, vec!["part 3"]);
+        assert_eq!(queued.delivered.unwrap().channel, "C0123");
+    }
+
+    #[test]
+    fn lock_is_exclusive_until_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let held = lock(dir.path()).unwrap();
+        let other = std::fs::File::open(dir.path().join(LOCK_FILE)).unwrap();
+        assert!(matches!(
+            other.try_lock(),
+            Err(std::fs::TryLockError::WouldBlock)
+        ));
+        drop(held);
+        other.try_lock().unwrap();
+    }
+
+    #[test]
+    fn list_skips_unfinished_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".20261016T090000000000-000000001.tmp")).unwrap();
+        std::fs::create_dir_all(dir.path().join("20261016T090000000000-000000002")).unwrap();
+        assert!(list(dir.path()).unwrap().is_empty());
+    }
+}
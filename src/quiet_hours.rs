@@ -0,0 +1,162 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveTime, TimeZone, Utc};
+
+/// A daily window like `22:00-07:00` during which sends are held back, in local time or
+/// at a fixed UTC offset (`22:00-07:00 +09:00`). The window may span midnight.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuietHours {
+    start: NaiveTime,
+    end: NaiveTime,
+    /// `None` = the machine's local time zone
+    offset: Option<FixedOffset>,
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M").with_context(|| format!("bad time '{value}'"))
+}
+
+fn parse_offset(value: &str) -> Result<FixedOffset> {
+    if value.eq_ignore_ascii_case("utc") || value == "Z" {
+        return Ok(FixedOffset::east_opt(0).expect("zero offset"));
+    }
+    value
+        .parse()
+        .with_context(|| format!("bad UTC offset '{value}'"))
+}
+
+impl FromStr for QuietHours {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            format!("invalid quiet_hours '{s}' (expected HH:MM-HH:MM, optionally followed by a UTC offset like +09:00)")
+        };
+        let mut parts = s.split_whitespace();
+        let range = parts.next().with_context(invalid)?;
+        let offset = parts
+            .next()
+            .map(parse_offset)
+            .transpose()
+            .with_context(invalid)?;
+        if parts.next().is_some() {
+            bail!(invalid());
+        }
+        let (start, end) = range.split_once('-').with_context(invalid)?;
+        let (start, end) = (
+            parse_time(start).with_context(invalid)?,
+            parse_time(end).with_context(invalid)?,
+        );
+        if start == end {
+            bail!("invalid quiet_hours '{s}' (start and end are the same)");
+        }
+        Ok(Self { start, end, offset })
+    }
+}
+
+impl fmt::Display for QuietHours {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )?;
+        if let Some(offset) = self.offset {
+            write!(f, " {offset}")?;
+        }
+        Ok(())
+    }
+}
+
+impl QuietHours {
+    /// When the window that `at` falls in ends, or `None` if `at` is outside quiet hours.
+    pub fn active_until(&self, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self.offset {
+            Some(offset) => self.until_in(&offset, at),
+            None => self.until_in(&Local, at),
+        }
+    }
+
+    fn until_in<Tz: TimeZone>(&self, tz: &Tz, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let local = at.with_timezone(tz).naive_local();
+        let time = local.time();
+        let active = if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        };
+        if !active {
+            return None;
+        }
+        let mut end = local.date().and_time(self.end);
+        if end <= local {
+            end += Duration::days(1);
+        }
+        // A DST jump can skip the end time; the first instant after the gap still ends the window
+        let end = tz.from_local_datetime(&end).earliest().or_else(|| {
+            tz.from_local_datetime(&(end + Duration::hours(1)))
+                .earliest()
+        })?;
+        Some(end.with_timezone(&Utc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[rstest]
+    #[case(
+        "22:00-07:00 +09:00",
+        "2026-01-01T14:00:00Z",
+        Some("2026-01-01T22:00:00Z")
+    )]
+    #[case(
+        "22:00-07:00 +09:00",
+        "2026-01-01T21:59:59Z",
+        Some("2026-01-01T22:00:00Z")
+    )]
+    #[case("22:00-07:00 +09:00", "2026-01-01T12:59:00Z", None)]
+    #[case("22:00-07:00 +09:00", "2026-01-01T22:00:00Z", None)]
+    #[case(
+        "22:00-07:00 UTC",
+        "2026-01-01T23:30:00Z",
+        Some("2026-01-02T07:00:00Z")
+    )]
+    #[case("12:00-13:00 Z", "2026-01-01T12:30:00Z", Some("2026-01-01T13:00:00Z"))]
+    #[case("12:00-13:00 Z", "2026-01-01T13:00:00Z", None)]
+    fn active_until_cases(#[case] quiet: &str, #[case] at: &str, #[case] expected: Option<&str>) {
+        let quiet: QuietHours = quiet.parse().unwrap();
+        assert_eq!(quiet.active_until(utc(at)), expected.map(utc));
+    }
+
+    #[rstest]
+    #[case("22:00-07:00", "22:00-07:00")]
+    #[case(" 22:00-07:00  +0900 ", "22:00-07:00 +09:00")]
+    #[case("22:00-07:00 utc", "22:00-07:00 +00:00")]
+    fn parse_valid(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(input.parse::<QuietHours>().unwrap().to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("", "invalid quiet_hours")]
+    #[case("22:00", "invalid quiet_hours")]
+    #[case("22-07", "invalid quiet_hours")]
+    #[case("25:00-07:00", "invalid quiet_hours")]
+    #[case("22:00-07:00 Asia/Tokyo", "invalid quiet_hours")]
+    #[case("22:00-07:00 +09:00 extra", "invalid quiet_hours")]
+    #[case("07:00-07:00", "start and end are the same")]
+    fn parse_invalid(#[case] input: &str, #[case] expected: &str) {
+        let err = input.parse::<QuietHours>().unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
@@ -0,0 +1,81 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+
+/// Slack refuses to schedule messages further out than this.
+const MAX_AHEAD_DAYS: i64 = 120;
+
+/// Parse `--at`: an RFC 3339 timestamp or a relative offset like `+30m`, `+2h`, `+1d`.
+pub fn parse_at(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    let at = match value.strip_prefix('+') {
+        Some(offset) => now + parse_offset(offset).with_context(|| invalid(value))?,
+        None => DateTime::parse_from_rfc3339(value)
+            .with_context(|| invalid(value))?
+            .with_timezone(&Utc),
+    };
+
+    if at <= now {
+        bail!("--at '{value}' is in the past");
+    }
+    if at - now > Duration::days(MAX_AHEAD_DAYS) {
+        bail!("--at '{value}' is more than {MAX_AHEAD_DAYS} days ahead (Slack limit)");
+    }
+    Ok(at)
+}
+
+fn invalid(value: &str) -> String {
+    format!("invalid --at '{value}' (expected RFC 3339 like 2026-01-02T09:00:00+09:00, or +30m / +2h / +1d)")
+}
+
+fn parse_offset(offset: &str) -> Result<Duration> {
+    let unit_at = offset
+        .find(|c: char| !c.is_ascii_digit())
+        .context("missing unit")?;
+    let (amount, unit) = offset.split_at(unit_at);
+    let amount: i64 = amount.parse().context("missing amount")?;
+    match unit {
+        "s" => Ok(Duration::seconds(amount)),
+        "m" => Ok(Duration::minutes(amount)),
+        "h" => Ok(Duration::hours(amount)),
+        "d" => Ok(Duration::days(amount)),
+        _ => bail!("unknown unit '{unit}'"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[rstest]
+    #[case("+90s", "2026-01-01T00:01:30Z")]
+    #[case("+30m", "2026-01-01T00:30:00Z")]
+    #[case("+2h", "2026-01-01T02:00:00Z")]
+    #[case("+1d", "2026-01-02T00:00:00Z")]
+    #[case("2026-01-02T09:00:00+09:00", "2026-01-02T00:00:00Z")]
+    #[case(" 2026-01-01T12:00:00Z ", "2026-01-01T12:00:00Z")]
+    fn parse_at_valid(#[case] input: &str, #[case] expected: &str) {
+        let expected = DateTime::parse_from_rfc3339(expected).unwrap();
+        assert_eq!(parse_at(input, now()).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("+30", "invalid --at")]
+    #[case("+m", "invalid --at")]
+    #[case("+5w", "invalid --at")]
+    #[case("tomorrow", "invalid --at")]
+    #[case("2025-12-31T23:00:00Z", "in the past")]
+    #[case("+0m", "in the past")]
+    #[case("+121d", "more than 120 days")]
+    fn parse_at_invalid(#[case] input: &str, #[case] expected: &str) {
+        let err = parse_at(input, now()).unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
@@ -0,0 +1,121 @@
+/// Bytes of a file's head needed to recognise the formats in `from_magic`.
+pub(super) const MAGIC_LEN: usize = 12;
+
+const OCTET_STREAM: &str = "application/octet-stream";
+
+fn extension(name: &str) -> Option<String> {
+    name.rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+}
+
+fn from_magic(head: &[u8]) -> Option<&'static str> {
+    Some(match head {
+        [0x89, b'P', b'N', b'G', ..] => "image/png",
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [b'G', b'I', b'F', b'8', ..] => "image/gif",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+        [b'%', b'P', b'D', b'F', ..] => "application/pdf",
+        [0x1F, 0x8B, ..] => "application/gzip",
+        [b'P', b'K', 0x03, 0x04, ..] => "application/zip",
+        _ => return None,
+    })
+}
+
+fn from_extension(ext: &str) -> Option<&'static str> {
+    Some(match ext {
+        "txt" | "log" => "text/plain",
+        "csv" => "text/csv",
+        "tsv" => "text/tab-separated-values",
+        "md" => "text/markdown",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "xml" => "application/xml",
+        "json" => "application/json",
+        "yml" | "yaml" => "application/yaml",
+        "svg" => "image/svg+xml",
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        "mp3" => "audio/mpeg",
+        _ => return None,
+    })
+}
+
+/// Content-Type for an upload: magic bytes first (the name may lie), then the extension.
+pub(super) fn detect(name: &str, head: &[u8]) -> &'static str {
+    from_magic(head)
+        .or_else(|| extension(name).as_deref().and_then(from_extension))
+        .unwrap_or(OCTET_STREAM)
+}
+
+/// Whether content of this type could be text worth reading (unknown types included).
+pub(super) fn may_be_text(content_type: &str) -> bool {
+    content_type.starts_with("text/")
+        || matches!(
+            content_type,
+            "application/json"
+                | "application/xml"
+                | "application/yaml"
+                | "image/svg+xml"
+                | OCTET_STREAM
+        )
+}
+
+/// Slack snippet type implied by a file's extension, for syntax highlighting.
+pub fn snippet_type_for(name: &str) -> Option<&'static str> {
+    Some(match extension(name)?.as_str() {
+        "rs" => "rust",
+        "py" => "python",
+        "js" | "mjs" => "javascript",
+        "ts" => "typescript",
+        "rb" => "ruby",
+        "go" => "go",
+        "java" => "java",
+        "kt" => "kotlin",
+        "c" | "h" => "c",
+        "cpp" | "cc" | "hpp" => "cpp",
+        "sh" | "bash" | "zsh" => "shell",
+        "sql" => "sql",
+        "diff" | "patch" => "diff",
+        "json" => "json",
+        "yml" | "yaml" => "yaml",
+        "md" => "markdown",
+        "html" | "htm" => "html",
+        "css" => "css",
+        "xml" => "xml",
+        "log" | "txt" => "text",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("shot.png", b"\x89PNG\r\n\x1a\n".as_slice(), "image/png")]
+    #[case("shot.dat", b"\xff\xd8\xff\xe0".as_slice(), "image/jpeg")]
+    #[case("photo.webp", b"RIFF\x00\x00\x00\x00WEBPVP8 ".as_slice(), "image/webp")]
+    #[case("report.txt", b"%PDF-1.7".as_slice(), "application/pdf")]
+    #[case("app.log.gz", b"\x1f\x8b\x08".as_slice(), "application/gzip")]
+    #[case("data.CSV", b"a,b\n1,2".as_slice(), "text/csv")]
+    #[case("notes.md", b"# hi".as_slice(), "text/markdown")]
+    #[case("blob", b"\x00\x01".as_slice(), "application/octet-stream")]
+    #[case("empty.bin", b"".as_slice(), "application/octet-stream")]
+    fn detect_cases(#[case] name: &str, #[case] head: &[u8], #[case] expected: &str) {
+        assert_eq!(detect(name, head), expected);
+    }
+
+    #[rstest]
+    #[case("main.rs", Some("rust"))]
+    #[case("fix.PATCH", Some("diff"))]
+    #[case("deploy.sh", Some("shell"))]
+    #[case("build.log", Some("text"))]
+    #[case("app.log.gz", None)]
+    #[case("photo.jpg", None)]
+    #[case("Makefile", None)]
+    fn snippet_type_cases(#[case] name: &str, #[case] expected: Option<&str>) {
+        assert_eq!(snippet_type_for(name), expected);
+    }
+}
@@ -0,0 +1,166 @@
+use anyhow::{bail, Context, Result};
+use secrecy::SecretString;
+use serde::Deserialize;
+
+use super::client::{check_ok, send_form};
+
+/// A message location parsed from a Slack permalink.
+#[derive(Debug, PartialEq)]
+pub struct Permalink {
+    pub channel: String,
+    pub ts: String,
+    pub thread_ts: Option<String>,
+}
+
+/// Parse `https://<workspace>.slack.com/archives/<channel>/p<ts>[?thread_ts=...]`.
+pub fn parse_permalink(url: &str) -> Result<Permalink> {
+    let invalid = || format!("invalid Slack permalink: '{url}'");
+
+    let rest = url
+        .trim()
+        .strip_prefix("https://")
+        .or_else(|| url.trim().strip_prefix("http://"))
+        .with_context(invalid)?;
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let mut segments = path.split('/');
+    let host = segments.next().unwrap_or_default();
+    if !host.ends_with(".slack.com") {
+        bail!("{}", invalid());
+    }
+    let (Some("archives"), Some(channel), Some(p), None) = (
+        segments.next(),
+        segments.next(),
+        segments.next().filter(|s| !s.is_empty()),
+        segments.next(),
+    ) else {
+        bail!("{}", invalid());
+    };
+
+    let digits = p.strip_prefix('p').with_context(invalid)?;
+    if digits.len() <= 6 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        bail!("{}", invalid());
+    }
+    let (secs, micros) = digits.split_at(digits.len() - 6);
+    let ts = format!("{secs}.{micros}");
+
+    let thread_ts = query
+        .split('&')
+        .filter_map(|kv| kv.split_once('='))
+        .find(|(k, _)| *k == "thread_ts")
+        .map(|(_, v)| v.to_string())
+        .filter(|v| *v != ts);
+
+    Ok(Permalink {
+        channel: channel.to_string(),
+        ts,
+        thread_ts,
+    })
+}
+
+#[derive(Deserialize)]
+struct HistoryResponse {
+    ok: bool,
+    error: Option<String>,
+    #[serde(default)]
+    messages: Vec<Message>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Message {
+    pub ts: String,
+    #[serde(default)]
+    pub text: String,
+    pub user: Option<String>,
+    #[serde(default)]
+    pub files: Vec<FileRef>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileRef {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub permalink: Option<String>,
+}
+
+/// Fetch a single message by channel and ts (thread replies need `thread_ts`).
+pub fn fetch_message(token: &SecretString, link: &Permalink) -> Result<Message> {
+    find_message(token, link)?
+        .with_context(|| format!("message {} not found in {}", link.ts, link.channel))
+}
+
+/// Like `fetch_message`, but a message that isn't there (yet) is `None` rather than an error.
+pub fn find_message(token: &SecretString, link: &Permalink) -> Result<Option<Message>> {
+    let (api, mut params) = match &link.thread_ts {
+        Some(thread_ts) => ("conversations.replies", vec![("ts", thread_ts.as_str())]),
+        None => ("conversations.history", vec![]),
+    };
+    params.extend([
+        ("channel", link.channel.as_str()),
+        ("latest", link.ts.as_str()),
+        ("oldest", link.ts.as_str()),
+        ("inclusive", "true"),
+        ("limit", "1"),
+    ]);
+
+    let mut resp =
+        send_form(token, api, params).with_context(|| format!("failed to call {api}"))?;
+    let body: HistoryResponse = resp
+        .body_mut()
+        .read_json()
+        .with_context(|| format!("failed to parse {api} response"))?;
+    check_ok(body.ok, body.error.as_deref(), api)?;
+
+    Ok(body.messages.into_iter().find(|m| m.ts == link.ts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_permalink_top_level() {
+        let link =
+            parse_permalink("https://acme.slack.com/archives/C0123ABCD/p1700000000123456").unwrap();
+        assert_eq!(
+            link,
+            Permalink {
+                channel: "C0123ABCD".to_string(),
+                ts: "1700000000.123456".to_string(),
+                thread_ts: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_permalink_thread_reply() {
+        let link = parse_permalink(
+            "https://acme.slack.com/archives/C0123ABCD/p1700000001000200?thread_ts=1700000000.123456&cid=C0123ABCD",
+        )
+        .unwrap();
+        assert_eq!(link.ts, "1700000001.000200");
+        assert_eq!(link.thread_ts.as_deref(), Some("1700000000.123456"));
+    }
+
+    #[test]
+    fn parse_permalink_thread_parent_has_no_thread_ts() {
+        let link = parse_permalink(
+            "https://acme.slack.com/archives/C01/p1700000000123456?thread_ts=1700000000.123456",
+        )
+        .unwrap();
+        assert!(link.thread_ts.is_none());
+    }
+
+    #[test]
+    fn parse_permalink_rejects_invalid() {
+        for url in [
+            "https://example.com/archives/C01/p1700000000123456",
+            "https://acme.slack.com/archives/C01",
+            "https://acme.slack.com/archives/C01/1700000000123456",
+            "https://acme.slack.com/archives/C01/pabc",
+            "https://acme.slack.com/messages/C01/p1700000000123456",
+            "not a url",
+        ] {
+            assert!(parse_permalink(url).is_err(), "expected error for {url}");
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::client::{check_ok, send_empty, send_form};
+
+/// How long cached `auth.test` / `team.info` results are reused.
+const CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AuthInfo {
+    pub url: String,
+    pub team: String,
+    pub user: String,
+    pub team_id: String,
+    pub user_id: String,
+    #[serde(default)]
+    pub bot_id: Option<String>,
+    /// Granted scopes from the `x-oauth-scopes` response header.
+    #[serde(default)]
+    pub scopes: Option<Vec<String>>,
+}
+
+impl AuthInfo {
+    /// Required scopes the token does not grant. Errors if Slack didn't report scopes.
+    pub fn missing_scopes<'a>(&self, required: &'a [String]) -> Result<Vec<&'a str>> {
+        if required.is_empty() {
+            return Ok(Vec::new());
+        }
+        let granted = self
+            .scopes
+            .as_ref()
+            .context("Slack did not report the token's scopes (x-oauth-scopes header missing)")?;
+        Ok(required
+            .iter()
+            .filter(|s| !granted.contains(s))
+            .map(String::as_str)
+            .collect())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TeamInfo {
+    pub id: String,
+    pub name: String,
+    pub domain: String,
+}
+
+/// Who the token belongs to. `team` is `None` when `team.info` is not permitted.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Identity {
+    pub auth: AuthInfo,
+    pub team: Option<TeamInfo>,
+}
+
+impl Identity {
+    /// Human-readable workspace name, falling back to auth.test's team name.
+    pub fn workspace_name(&self) -> &str {
+        self.team
+            .as_ref()
+            .map(|t| t.name.as_str())
+            .unwrap_or(&self.auth.team)
+    }
+}
+
+/// The app behind a bot user, from bots.info.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BotInfo {
+    pub name: String,
+    #[serde(default)]
+    pub app_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BotsInfoResponse {
+    ok: bool,
+    error: Option<String>,
+    bot: Option<BotInfo>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct CacheEntry {
+    fetched_at: u64,
+    identity: Identity,
+}
+
+#[derive(Deserialize)]
+struct AuthTestResponse {
+    ok: bool,
+    error: Option<String>,
+    #[serde(flatten)]
+    info: Option<AuthInfo>,
+}
+
+#[derive(Deserialize)]
+struct TeamInfoResponse {
+    ok: bool,
+    error: Option<String>,
+    team: Option<TeamInfo>,
+}
+
+pub fn auth_test(token: &SecretString) -> Result<AuthInfo> {
+    let mut resp = send_empty(token, "auth.test").context("failed to call auth.test")?;
+    let scopes = resp
+        .headers()
+        .get("x-oauth-scopes")
+        .and_then(|v| v.to_str().ok())
+        .map(parse_scopes);
+    let body: AuthTestResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse auth.test response")?;
+    check_ok(body.ok, body.error.as_deref(), "auth.test")?;
+    let mut info = body
+        .info
+        .context("missing identity in auth.test response")?;
+    info.scopes = scopes;
+    Ok(info)
+}
+
+pub fn team_info(token: &SecretString) -> Result<TeamInfo> {
+    let mut resp = send_empty(token, "team.info").context("failed to call team.info")?;
+    let body: TeamInfoResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse team.info response")?;
+    check_ok(body.ok, body.error.as_deref(), "team.info")?;
+    body.team.context("missing team in team.info response")
+}
+
+/// Look up a bot's app (needs `users:read`).
+pub fn bot_info(token: &SecretString, bot_id: &str) -> Result<BotInfo> {
+    let mut resp =
+        send_form(token, "bots.info", [("bot", bot_id)]).context("failed to call bots.info")?;
+    let body: BotsInfoResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse bots.info response")?;
+    check_ok(body.ok, body.error.as_deref(), "bots.info")?;
+    body.bot.context("missing bot in bots.info response")
+}
+
+fn parse_scopes(header: &str) -> Vec<String> {
+    header
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Short, non-reversible identifier for a token, used as the cache key.
+pub fn fingerprint(token: &SecretString) -> String {
+    let digest = Sha256::digest(token.expose_secret().as_bytes());
+    digest[..8].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn cache_dir() -> Result<PathBuf> {
+    let dir = dirs::cache_dir().context("could not determine cache directory")?;
+    Ok(dir.join("slafling").join("identity"))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn read_cache(path: &Path, now: u64) -> Option<Identity> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+    let age = now.checked_sub(entry.fetched_at)?;
+    (age < CACHE_TTL.as_secs()).then_some(entry.identity)
+}
+
+fn write_cache(path: &Path, identity: &Identity, now: u64) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+    let entry = CacheEntry {
+        fetched_at: now,
+        identity: identity.clone(),
+    };
+    let json = serde_json::to_string(&entry).context("failed to serialize identity cache")?;
+    std::fs::write(path, json).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Look up the token's identity, reusing a cached result younger than the TTL.
+pub fn identity(token: &SecretString) -> Result<Identity> {
+    let path = cache_dir()?.join(fingerprint(token));
+    let now = now_secs();
+    if let Some(identity) = read_cache(&path, now) {
+        return Ok(identity);
+    }
+
+    let identity = Identity {
+        auth: auth_test(token)?,
+        team: team_info(token).ok(),
+    };
+    // A failed cache write only costs an extra round trip next time.
+    let _ = write_cache(&path, &identity, now);
+    Ok(identity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_identity() -> Identity {
+        Identity {
+            auth: AuthInfo {
+                url: "https://acme.slack.com/".to_string(),
+                team: "Acme".to_string(),
+                user: "slafling".to_string(),
+                team_id: "T01".to_string(),
+                user_id: "U01".to_string(),
+                bot_id: Some("B01".to_string()),
+                scopes: Some(vec!["chat:write".to_string()]),
+            },
+            team: None,
+        }
+    }
+
+    #[test]
+    fn fingerprint_is_stable_and_hides_token() {
+        let token: SecretString = "xoxb-123-456".into();
+        let fp = fingerprint(&token);
+        assert_eq!(fp, fingerprint(&"xoxb-123-456".into()));
+        assert_ne!(fp, fingerprint(&"xoxb-123-457".into()));
+        assert_eq!(fp.len(), 16);
+        assert!(!fp.contains("xoxb"));
+    }
+
+    #[test]
+    fn parse_scopes_header() {
+        assert_eq!(
+            parse_scopes("chat:write, files:write,channels:read"),
+            vec!["chat:write", "files:write", "channels:read"]
+        );
+        assert!(parse_scopes("").is_empty());
+    }
+
+    #[test]
+    fn cache_roundtrip_within_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("identity").join("abc");
+        write_cache(&path, &sample_identity(), 1_000).unwrap();
+        let cached = read_cache(&path, 1_000 + CACHE_TTL.as_secs() - 1).unwrap();
+        assert_eq!(cached.auth.user_id, "U01");
+        assert_eq!(cached.workspace_name(), "Acme");
+    }
+
+    #[test]
+    fn cache_expires_after_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("abc");
+        write_cache(&path, &sample_identity(), 1_000).unwrap();
+        assert!(read_cache(&path, 1_000 + CACHE_TTL.as_secs()).is_none());
+    }
+
+    #[test]
+    fn cache_missing_or_corrupt_is_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("abc");
+        assert!(read_cache(&path, 0).is_none());
+        std::fs::write(&path, "not json").unwrap();
+        assert!(read_cache(&path, 0).is_none());
+    }
+
+    #[test]
+    fn auth_test_response_parses() {
+        let json = r#"{"ok":true,"url":"https://acme.slack.com/","team":"Acme","user":"bot","team_id":"T1","user_id":"U1","bot_id":"B1"}"#;
+        let body: AuthTestResponse = serde_json::from_str(json).unwrap();
+        let info = body.info.unwrap();
+        assert_eq!(info.team_id, "T1");
+        assert!(info.scopes.is_none());
+    }
+
+    #[test]
+    fn missing_scopes_lists_ungranted() {
+        let auth = sample_identity().auth;
+        let required = vec!["chat:write".to_string(), "files:write".to_string()];
+        assert_eq!(auth.missing_scopes(&required).unwrap(), vec!["files:write"]);
+        assert!(auth.missing_scopes(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn missing_scopes_errors_without_header() {
+        let mut auth = sample_identity().auth;
+        auth.scopes = None;
+        assert!(auth.missing_scopes(&["chat:write".to_string()]).is_err());
+        assert!(auth.missing_scopes(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn workspace_name_prefers_team_info() {
+        let mut identity = sample_identity();
+        identity.team = Some(TeamInfo {
+            id: "T01".to_string(),
+            name: "Acme Corp".to_string(),
+            domain: "acme".to_string(),
+        });
+        assert_eq!(identity.workspace_name(), "Acme Corp");
+    }
+}
@@ -1,33 +1,541 @@
 mod client;
+mod content_type;
+mod history;
+mod identity;
+mod presence;
+mod reactions;
+mod scheduled;
+mod usergroups;
+mod users;
 
-use anyhow::{Context, Result};
+use std::io::{BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use anyhow::{bail, Context, Result};
+use secrecy::SecretString;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use crate::cli::ChannelType;
-use client::{check_ok, slack_post, OkResponse};
+use client::{check_ok, send_form, send_json, upload_post, with_retry, OkResponse};
+pub use client::{
+    configure_http, is_auth_failure, is_channel_not_found, is_not_in_channel, is_rate_limited,
+    is_unreachable, is_unsent_failure, parse_api_base, parse_proxy, set_retry_policy, HttpOptions,
+    RetryPolicy, SCOPE_USES,
+};
+pub use content_type::snippet_type_for;
+pub use history::{fetch_message, find_message, parse_permalink, Message, Permalink};
+pub use identity::{auth_test, bot_info, fingerprint, identity};
+pub use presence::{dnd_info, get_presence};
+pub use reactions::{emoji_name, get_reactions, remove_reaction};
+pub use scheduled::{delete_scheduled, list_scheduled};
+pub use usergroups::list_usergroups;
+pub use users::{is_user_id, list_members, lookup_member_by_email, resolve_user_id, Member};
 
 // --- chat.postMessage ---
 
+/// Optional chat.postMessage parameters beyond channel and text.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MessageOptions {
+    pub blocks: Option<Vec<serde_json::Value>>,
+    pub attachments: Option<Vec<serde_json::Value>>,
+    /// `Some(false)` sends the text literally (no *bold*, _italic_, ...)
+    pub mrkdwn: Option<bool>,
+    pub unfurl_links: Option<bool>,
+    pub unfurl_media: Option<bool>,
+    pub link_names: Option<bool>,
+    pub thread_ts: Option<String>,
+}
+
 #[derive(Serialize)]
 struct PostMessageBody<'a> {
     channel: &'a str,
     text: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blocks: Option<&'a [serde_json::Value]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attachments: Option<&'a [serde_json::Value]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_ts: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mrkdwn: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unfurl_links: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unfurl_media: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link_names: Option<bool>,
 }
 
-pub fn post_message(token: &str, channel: &str, text: &str) -> Result<()> {
-    let body = PostMessageBody { channel, text };
-    let mut resp = slack_post(token, "chat.postMessage")
-        .send_json(&body)
-        .context("failed to call chat.postMessage")?;
-    let result: OkResponse = resp
+impl<'a> PostMessageBody<'a> {
+    fn new(channel: &'a str, text: &'a str, options: &'a MessageOptions) -> Self {
+        Self {
+            channel,
+            text,
+            blocks: options.blocks.as_deref(),
+            attachments: options.attachments.as_deref(),
+            thread_ts: options.thread_ts.as_deref(),
+            mrkdwn: options.mrkdwn,
+            unfurl_links: options.unfurl_links,
+            unfurl_media: options.unfurl_media,
+            link_names: options.link_names,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PostMessageResponse {
+    ok: bool,
+    error: Option<String>,
+    channel: Option<String>,
+    ts: Option<String>,
+}
+
+/// Where a posted message landed.
+#[derive(Debug)]
+pub struct PostedMessage {
+    /// Conversation ID (even when the message was sent to a `#name`)
+    pub channel: String,
+    pub ts: String,
+}
+
+pub fn post_message(
+    token: &SecretString,
+    channel: &str,
+    text: &str,
+    options: &MessageOptions,
+) -> Result<PostedMessage> {
+    let body = PostMessageBody::new(channel, text, options);
+    let mut resp =
+        send_json(token, "chat.postMessage", &body).context("failed to call chat.postMessage")?;
+    let result: PostMessageResponse = resp
         .body_mut()
         .read_json()
         .context("failed to parse chat.postMessage response")?;
-    check_ok(result.ok, result.error.as_deref(), "chat.postMessage")
+    check_ok(result.ok, result.error.as_deref(), "chat.postMessage")?;
+    Ok(PostedMessage {
+        channel: result.channel.context("missing channel in response")?,
+        ts: result.ts.context("missing ts in response")?,
+    })
+}
+
+// --- chat.meMessage ---
+
+#[derive(Serialize)]
+struct MeMessageBody<'a> {
+    channel: &'a str,
+    text: &'a str,
+}
+
+/// Post a `/me`-style message (rendered in italics as an action by the bot).
+pub fn me_message(token: &SecretString, channel: &str, text: &str) -> Result<PostedMessage> {
+    let body = MeMessageBody { channel, text };
+    let mut resp =
+        send_json(token, "chat.meMessage", &body).context("failed to call chat.meMessage")?;
+    let result: PostMessageResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse chat.meMessage response")?;
+    check_ok(result.ok, result.error.as_deref(), "chat.meMessage")?;
+    Ok(PostedMessage {
+        channel: result.channel.context("missing channel in response")?,
+        ts: result.ts.context("missing ts in response")?,
+    })
+}
+
+// --- chat.postEphemeral ---
+
+#[derive(Serialize)]
+struct PostEphemeralBody<'a> {
+    channel: &'a str,
+    user: &'a str,
+    text: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blocks: Option<&'a [serde_json::Value]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attachments: Option<&'a [serde_json::Value]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_ts: Option<&'a str>,
+}
+
+impl<'a> PostEphemeralBody<'a> {
+    fn new(channel: &'a str, user: &'a str, text: &'a str, options: &'a MessageOptions) -> Self {
+        Self {
+            channel,
+            user,
+            text,
+            blocks: options.blocks.as_deref(),
+            attachments: options.attachments.as_deref(),
+            thread_ts: options.thread_ts.as_deref(),
+        }
+    }
+}
+
+/// Post a message only `user` (a user ID) can see; it is not stored in channel history.
+pub fn post_ephemeral(
+    token: &SecretString,
+    channel: &str,
+    user: &str,
+    text: &str,
+    options: &MessageOptions,
+) -> Result<()> {
+    let body = PostEphemeralBody::new(channel, user, text, options);
+    let mut resp = send_json(token, "chat.postEphemeral", &body)
+        .context("failed to call chat.postEphemeral")?;
+    let result: OkResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse chat.postEphemeral response")?;
+    check_ok(result.ok, result.error.as_deref(), "chat.postEphemeral")
+}
+
+// --- chat.scheduleMessage ---
+
+#[derive(Serialize)]
+struct ScheduleMessageBody<'a> {
+    channel: &'a str,
+    text: &'a str,
+    post_at: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blocks: Option<&'a [serde_json::Value]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attachments: Option<&'a [serde_json::Value]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_ts: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mrkdwn: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unfurl_links: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unfurl_media: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link_names: Option<bool>,
+}
+
+impl<'a> ScheduleMessageBody<'a> {
+    fn new(channel: &'a str, text: &'a str, post_at: i64, options: &'a MessageOptions) -> Self {
+        Self {
+            channel,
+            text,
+            post_at,
+            blocks: options.blocks.as_deref(),
+            attachments: options.attachments.as_deref(),
+            thread_ts: options.thread_ts.as_deref(),
+            mrkdwn: options.mrkdwn,
+            unfurl_links: options.unfurl_links,
+            unfurl_media: options.unfurl_media,
+            link_names: options.link_names,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ScheduleMessageResponse {
+    ok: bool,
+    error: Option<String>,
+    channel: Option<String>,
+    scheduled_message_id: Option<String>,
+}
+
+/// A message queued by chat.scheduleMessage.
+#[derive(Debug)]
+pub struct ScheduledMessage {
+    pub channel: String,
+    pub id: String,
+}
+
+/// Schedule a message for `post_at` (Unix seconds).
+pub fn schedule_message(
+    token: &SecretString,
+    channel: &str,
+    text: &str,
+    post_at: i64,
+    options: &MessageOptions,
+) -> Result<ScheduledMessage> {
+    let body = ScheduleMessageBody::new(channel, text, post_at, options);
+    let mut resp = send_json(token, "chat.scheduleMessage", &body)
+        .context("failed to call chat.scheduleMessage")?;
+    let result: ScheduleMessageResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse chat.scheduleMessage response")?;
+    check_ok(result.ok, result.error.as_deref(), "chat.scheduleMessage")?;
+    Ok(ScheduledMessage {
+        channel: result.channel.context("missing channel in response")?,
+        id: result
+            .scheduled_message_id
+            .context("missing scheduled_message_id in response")?,
+    })
+}
+
+// --- Dry run ---
+
+/// Which method a text send goes through.
+pub enum Delivery<'a> {
+    Post,
+    Me,
+    Ephemeral { user: &'a str },
+    Schedule { post_at: i64 },
+}
+
+/// The method and exact JSON body a text send would use, without calling it.
+pub fn message_request(
+    delivery: &Delivery,
+    channel: &str,
+    text: &str,
+    options: &MessageOptions,
+) -> (&'static str, serde_json::Value) {
+    let body = match *delivery {
+        Delivery::Post => serde_json::to_value(PostMessageBody::new(channel, text, options)),
+        Delivery::Me => serde_json::to_value(MeMessageBody { channel, text }),
+        Delivery::Ephemeral { user } => {
+            serde_json::to_value(PostEphemeralBody::new(channel, user, text, options))
+        }
+        Delivery::Schedule { post_at } => {
+            serde_json::to_value(ScheduleMessageBody::new(channel, text, post_at, options))
+        }
+    };
+    let method = match delivery {
+        Delivery::Post => "chat.postMessage",
+        Delivery::Me => "chat.meMessage",
+        Delivery::Ephemeral { .. } => "chat.postEphemeral",
+        Delivery::Schedule { .. } => "chat.scheduleMessage",
+    };
+    (method, body.expect("message body serializes"))
+}
+
+// --- chat.getPermalink ---
+
+#[derive(Deserialize)]
+struct PermalinkResponse {
+    ok: bool,
+    error: Option<String>,
+    permalink: Option<String>,
+}
+
+pub fn get_permalink(token: &SecretString, channel: &str, ts: &str) -> Result<String> {
+    let mut resp = send_form(
+        token,
+        "chat.getPermalink",
+        [("channel", channel), ("message_ts", ts)],
+    )
+    .context("failed to call chat.getPermalink")?;
+    let body: PermalinkResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse chat.getPermalink response")?;
+    check_ok(body.ok, body.error.as_deref(), "chat.getPermalink")?;
+    body.permalink.context("missing permalink in response")
+}
+
+// --- chat.update ---
+
+#[derive(Serialize)]
+struct UpdateMessageBody<'a> {
+    channel: &'a str,
+    ts: &'a str,
+    text: &'a str,
+}
+
+/// Replace a message's text via chat.update (channel must be a conversation ID).
+pub fn update_message(token: &SecretString, channel: &str, ts: &str, text: &str) -> Result<()> {
+    let body = UpdateMessageBody { channel, ts, text };
+    let mut resp = send_json(token, "chat.update", &body).context("failed to call chat.update")?;
+    let result: OkResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse chat.update response")?;
+    check_ok(result.ok, result.error.as_deref(), "chat.update")
+}
+
+// --- chat.delete ---
+
+#[derive(Serialize)]
+struct DeleteMessageBody<'a> {
+    channel: &'a str,
+    ts: &'a str,
+}
+
+/// Delete a message via chat.delete (channel must be a conversation ID).
+pub fn delete_message(token: &SecretString, channel: &str, ts: &str) -> Result<()> {
+    let body = DeleteMessageBody { channel, ts };
+    let mut resp = send_json(token, "chat.delete", &body).context("failed to call chat.delete")?;
+    let result: OkResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse chat.delete response")?;
+    check_ok(result.ok, result.error.as_deref(), "chat.delete")
 }
 
 // --- File upload (3-step) ---
 
+/// A file to upload. Content is read from disk while sending, never held in memory whole;
+/// only small generated content (snippets) lives in a buffer.
+pub struct UploadFile {
+    pub name: String,
+    pub len: u64,
+    /// Syntax highlighting hint for Slack (e.g. `python`, `diff`); Slack guesses when unset
+    pub snippet_type: Option<String>,
+    /// Display title in Slack; the filename when unset
+    pub title: Option<String>,
+    source: UploadSource,
+}
+
+enum UploadSource {
+    Path(PathBuf),
+    /// A temporary spool file, removed when the `UploadFile` is dropped
+    Spool(PathBuf),
+    Bytes(Vec<u8>),
+}
+
+impl UploadFile {
+    pub fn from_path(name: String, path: &Path) -> Result<Self> {
+        let len = std::fs::metadata(path)
+            .with_context(|| format!("failed to read file: {}", path.display()))?
+            .len();
+        Ok(Self {
+            name,
+            len,
+            snippet_type: None,
+            title: None,
+            source: UploadSource::Path(path.to_path_buf()),
+        })
+    }
+
+    pub fn from_bytes(name: String, data: Vec<u8>) -> Self {
+        Self {
+            name,
+            len: data.len() as u64,
+            snippet_type: None,
+            title: None,
+            source: UploadSource::Bytes(data),
+        }
+    }
+
+    /// Copy a stream of unknown length (stdin) to a temp file, since Slack needs the length up front.
+    /// Fails once more than `limit` bytes arrive instead of filling the disk.
+    pub fn spool(name: String, reader: impl Read, limit: u64) -> Result<Self> {
+        Self::spool_with(name, |file| {
+            std::io::copy(&mut reader.take(limit + 1), file).context("failed to read input")?;
+            Ok(())
+        })
+    }
+
+    /// Like `spool`, with the content written by `write`, e.g. a filtered copy of another upload.
+    pub fn spool_with(
+        name: String,
+        write: impl FnOnce(&mut dyn Write) -> Result<()>,
+    ) -> Result<Self> {
+        let path = std::env::temp_dir().join(format!(
+            "slafling-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let file = options
+            .open(&path)
+            .with_context(|| format!("failed to create {}", path.display()))?;
+        // Owned from here on, so the spool is removed even if writing fails
+        let mut upload = Self {
+            name,
+            len: 0,
+            snippet_type: None,
+            title: None,
+            source: UploadSource::Spool(path),
+        };
+        let mut file = std::io::BufWriter::new(file);
+        write(&mut file)?;
+        let file = file
+            .into_inner()
+            .map_err(|e| e.into_error())
+            .context("failed to write spool file")?;
+        upload.len = file.metadata().context("failed to read spool file")?.len();
+        Ok(upload)
+    }
+
+    /// Like `spool`, but gzips the content on the way and appends `.gz` to the name.
+    /// `limit` applies to the compressed size, so input larger than it can still fit.
+    pub fn spool_gzip(name: String, reader: impl Read, limit: u64) -> Result<Self> {
+        let encoder = flate2::read::GzEncoder::new(reader, flate2::Compression::default());
+        Self::spool(format!("{name}.gz"), encoder, limit)
+    }
+
+    /// Hex SHA-256 of the content as it will be uploaded (after any compression).
+    pub fn sha256(&self) -> Result<String> {
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut self.reader()?, &mut hasher)
+            .with_context(|| format!("failed to read {}", self.name))?;
+        Ok(hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect())
+    }
+
+    /// Write the content as it would be uploaded to `path` (mode 0600 on Unix).
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options
+            .open(path)
+            .with_context(|| format!("failed to create {}", path.display()))?;
+        std::io::copy(&mut self.reader()?, &mut file)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    /// A buffered reader over the content if it may be text, or `None` for binary file types.
+    /// Whether it really is UTF-8 is left to the caller, which reads it as a stream.
+    pub fn text_reader(&self) -> Result<Option<impl BufRead + '_>> {
+        if !content_type::may_be_text(self.content_type()?) {
+            return Ok(None);
+        }
+        Ok(Some(std::io::BufReader::new(self.reader()?)))
+    }
+
+    /// Content-Type sent with the content, from the magic bytes or the name.
+    fn content_type(&self) -> Result<&'static str> {
+        let mut head = Vec::with_capacity(content_type::MAGIC_LEN);
+        self.reader()?
+            .take(content_type::MAGIC_LEN as u64)
+            .read_to_end(&mut head)
+            .with_context(|| format!("failed to read {}", self.name))?;
+        Ok(content_type::detect(&self.name, &head))
+    }
+
+    fn reader(&self) -> Result<Box<dyn Read + '_>> {
+        Ok(match &self.source {
+            UploadSource::Path(path) | UploadSource::Spool(path) => Box::new(
+                std::fs::File::open(path)
+                    .with_context(|| format!("failed to open {}", path.display()))?,
+            ),
+            UploadSource::Bytes(data) => Box::new(data.as_slice()),
+        })
+    }
+}
+
+impl Drop for UploadFile {
+    fn drop(&mut self) {
+        if let UploadSource::Spool(path) = &self.source {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
 #[derive(Deserialize)]
 struct GetUploadUrlResponse {
     ok: bool,
@@ -36,10 +544,13 @@ struct GetUploadUrlResponse {
     file_id: Option<String>,
 }
 
-fn get_upload_url(token: &str, filename: &str, length: u64) -> Result<(String, String)> {
-    let length_str = length.to_string();
-    let mut resp = slack_post(token, "files.getUploadURLExternal")
-        .send_form([("filename", filename), ("length", &length_str)])
+fn get_upload_url(token: &SecretString, file: &UploadFile) -> Result<(String, String)> {
+    let length = file.len.to_string();
+    let mut form = vec![("filename", file.name.as_str()), ("length", &length)];
+    if let Some(snippet_type) = &file.snippet_type {
+        form.push(("snippet_type", snippet_type));
+    }
+    let mut resp = send_form(token, "files.getUploadURLExternal", form)
         .context("failed to call files.getUploadURLExternal")?;
     let body: GetUploadUrlResponse = resp
         .body_mut()
@@ -51,11 +562,45 @@ fn get_upload_url(token: &str, filename: &str, length: u64) -> Result<(String, S
     Ok((upload_url, file_id))
 }
 
-fn upload_file_content(upload_url: &str, data: &[u8]) -> Result<()> {
-    ureq::post(upload_url)
-        .content_type("application/octet-stream")
-        .send(data)
-        .context("failed to upload file content")?;
+/// Called with a file's index in the upload and the bytes of it sent so far.
+/// Files upload concurrently, so calls for different files interleave.
+pub type UploadProgress<'a> = dyn FnMut(usize, u64) + Send + 'a;
+
+struct ProgressReader<'a, R> {
+    inner: R,
+    sent: u64,
+    report: &'a dyn Fn(u64),
+}
+
+impl<R: Read> Read for ProgressReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n > 0 {
+            self.sent += n as u64;
+            (self.report)(self.sent);
+        }
+        Ok(n)
+    }
+}
+
+fn upload_file_content(upload_url: &str, file: &UploadFile, report: &dyn Fn(u64)) -> Result<()> {
+    let content_type = file.content_type()?;
+    // Each attempt re-reads the content from the start
+    with_retry("file upload", || {
+        let inner = file
+            .reader()
+            .map_err(|e| ureq::Error::Io(std::io::Error::other(format!("{e:#}"))))?;
+        let mut reader = ProgressReader {
+            inner,
+            sent: 0,
+            report,
+        };
+        upload_post(upload_url)
+            .content_type(content_type)
+            .header("Content-Length", file.len)
+            .send(ureq::SendBody::from_reader(&mut reader))
+    })
+    .context("failed to upload file content")?;
     Ok(())
 }
 
@@ -72,48 +617,197 @@ struct CompleteUploadBody {
     channel_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     initial_comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_ts: Option<String>,
 }
 
 fn complete_upload(
-    token: &str,
-    file_id: &str,
-    title: &str,
+    token: &SecretString,
+    files: Vec<FileEntry>,
     channel: &str,
     initial_comment: Option<&str>,
+    thread_ts: Option<&str>,
 ) -> Result<()> {
     let body = CompleteUploadBody {
-        files: vec![FileEntry {
-            id: file_id.to_string(),
-            title: title.to_string(),
-        }],
+        files,
         channel_id: Some(channel.to_string()),
         initial_comment: initial_comment.map(String::from),
+        thread_ts: thread_ts.map(String::from),
     };
-    let mut resp = slack_post(token, "files.completeUploadExternal")
-        .send_json(&body)
-        .context("failed to call files.completeUploadExternal")?;
-    let result: OkResponse = resp
-        .body_mut()
-        .read_json()
-        .context("failed to parse files.completeUploadExternal response")?;
-    check_ok(
-        result.ok,
-        result.error.as_deref(),
-        "files.completeUploadExternal",
-    )
+
+    let mut attempt = 1;
+    loop {
+        let mut resp = send_json(token, "files.completeUploadExternal", &body)
+            .context("failed to call files.completeUploadExternal")?;
+        let result: OkResponse = resp
+            .body_mut()
+            .read_json()
+            .context("failed to parse files.completeUploadExternal response")?;
+
+        // Slack may not have finished processing the content POST yet
+        if !result.ok
+            && attempt < COMPLETE_UPLOAD_ATTEMPTS
+            && result
+                .error
+                .as_deref()
+                .is_some_and(is_transient_upload_error)
+        {
+            std::thread::sleep(COMPLETE_UPLOAD_RETRY_DELAY * attempt);
+            attempt += 1;
+            continue;
+        }
+
+        return check_ok(
+            result.ok,
+            result.error.as_deref(),
+            "files.completeUploadExternal",
+        );
+    }
+}
+
+const COMPLETE_UPLOAD_ATTEMPTS: u32 = 4;
+const COMPLETE_UPLOAD_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Errors meaning the uploaded content isn't processed yet. Not `internal_error` and the
+/// like: Slack may have shared the files anyway, and a retry would post them twice.
+fn is_transient_upload_error(error: &str) -> bool {
+    matches!(error, "file_not_found" | "file_upload_not_complete")
 }
 
-pub fn upload_file_bytes(
-    token: &str,
+/// Files sent at once; enough to overlap request latency without tripping rate limits.
+const MAX_PARALLEL_UPLOADS: usize = 4;
+
+/// Get an upload URL for one file and send its content; returns the file ID.
+fn upload_one(token: &SecretString, file: &UploadFile, report: &dyn Fn(u64)) -> Result<String> {
+    let (upload_url, file_id) = get_upload_url(token, file)?;
+    upload_file_content(&upload_url, file, report)
+        .with_context(|| format!("failed to upload {}", file.name))?;
+    Ok(file_id)
+}
+
+/// Upload files and share them together as one message.
+/// Files are uploaded on up to `MAX_PARALLEL_UPLOADS` threads, then completed in one call
+/// in their original order. After the first failure no new files are started.
+pub fn upload_files(
+    token: &SecretString,
     channel: &str,
-    filename: &str,
-    data: &[u8],
+    files: &[UploadFile],
     initial_comment: Option<&str>,
+    thread_ts: Option<&str>,
+    on_progress: Option<&mut UploadProgress<'_>>,
 ) -> Result<()> {
-    let (upload_url, file_id) = get_upload_url(token, filename, data.len() as u64)?;
-    upload_file_content(&upload_url, data)?;
-    complete_upload(token, &file_id, filename, channel, initial_comment)?;
-    Ok(())
+    let on_progress = Mutex::new(on_progress);
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let results: Mutex<Vec<Option<Result<String>>>> =
+        Mutex::new(files.iter().map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..files.len().min(MAX_PARALLEL_UPLOADS) {
+            scope.spawn(|| {
+                while !failed.load(Ordering::Relaxed) {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(file) = files.get(index) else { break };
+                    let report = |sent| {
+                        if let Some(on_progress) = on_progress.lock().unwrap().as_deref_mut() {
+                            on_progress(index, sent);
+                        }
+                    };
+                    let result = upload_one(token, file, &report);
+                    if result.is_err() {
+                        failed.store(true, Ordering::Relaxed);
+                    }
+                    results.lock().unwrap()[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    let mut entries = Vec::with_capacity(files.len());
+    for (file, result) in files.iter().zip(results.into_inner().unwrap()) {
+        // Files are claimed in order, so any skipped file comes after the failure returned here
+        let file_id = result.expect("file skipped without an earlier failure")?;
+        entries.push(FileEntry {
+            id: file_id,
+            title: file.title.clone().unwrap_or_else(|| file.name.clone()),
+        });
+    }
+    complete_upload(token, entries, channel, initial_comment, thread_ts)
+}
+
+// --- conversations.open ---
+
+#[derive(Deserialize)]
+struct ConversationsOpenResponse {
+    ok: bool,
+    error: Option<String>,
+    channel: Option<OpenedChannel>,
+}
+
+#[derive(Deserialize)]
+struct OpenedChannel {
+    id: String,
+}
+
+/// Open (or reuse) the DM with one user, or the group DM with several, and return its ID.
+pub fn open_conversation(token: &SecretString, users: &[String]) -> Result<String> {
+    let users = users.join(",");
+    let mut resp = send_form(token, "conversations.open", [("users", users.as_str())])
+        .context("failed to call conversations.open")?;
+    let body: ConversationsOpenResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse conversations.open response")?;
+    check_ok(body.ok, body.error.as_deref(), "conversations.open")?;
+    Ok(body.channel.context("missing channel in response")?.id)
+}
+
+// --- conversations.info ---
+
+#[derive(Deserialize)]
+struct ConversationsInfoResponse {
+    ok: bool,
+    error: Option<String>,
+    channel: Option<Channel>,
+}
+
+/// Name and kind of a conversation given by ID (needs the matching `*:read` scope).
+pub fn conversation_info(token: &SecretString, channel: &str) -> Result<ChannelInfo> {
+    let mut resp = send_form(token, "conversations.info", [("channel", channel)])
+        .context("failed to call conversations.info")?;
+    let body: ConversationsInfoResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse conversations.info response")?;
+    check_ok(body.ok, body.error.as_deref(), "conversations.info")?;
+    let ch = body.channel.context("missing channel in response")?;
+    Ok(ChannelInfo {
+        name: ch.name.clone().unwrap_or_else(|| ch.id.clone()),
+        channel_type: ch.channel_type(),
+        is_shared: ch.is_shared_externally(),
+        channel_id: ch.id,
+        user_id: ch.user,
+        is_member: ch.is_member,
+    })
+}
+
+// --- conversations.join ---
+
+/// Join a public channel (`#name` or ID) so the app can post there. Private channels
+/// need an invitation, so they are refused up front rather than by Slack.
+pub fn join_channel(token: &SecretString, channel: &str) -> Result<()> {
+    let id = resolve_channel_id(token, channel)?;
+    let info = conversation_info(token, &id)?;
+    if info.channel_type != ChannelType::PublicChannel {
+        bail!("auto_join only joins public channels; invite the app to {channel} instead");
+    }
+    let mut resp = send_form(token, "conversations.join", [("channel", id.as_str())])
+        .context("failed to call conversations.join")?;
+    let body: OkResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse conversations.join response")?;
+    check_ok(body.ok, body.error.as_deref(), "conversations.join")
 }
 
 // --- Channel search ---
@@ -138,10 +832,21 @@ struct Channel {
     is_mpim: bool,
     #[serde(default)]
     is_private: bool,
+    /// Only reported for channels, not DMs
+    is_member: Option<bool>,
+    #[serde(default)]
+    is_ext_shared: bool,
+    #[serde(default)]
+    is_shared: bool,
     user: Option<String>,
 }
 
 impl Channel {
+    /// Shared with another organization (Slack Connect)
+    fn is_shared_externally(&self) -> bool {
+        self.is_ext_shared || self.is_shared
+    }
+
     fn channel_type(&self) -> ChannelType {
         if self.is_im {
             ChannelType::Im
@@ -168,10 +873,16 @@ pub struct ChannelInfo {
     pub channel_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user_id: Option<String>,
+    /// Whether the token's user is in the channel (`None` for DMs)
+    #[serde(skip)]
+    pub is_member: Option<bool>,
+    /// Shared with another organization (Slack Connect)
+    #[serde(skip)]
+    pub is_shared: bool,
 }
 
 pub fn search_channels(
-    token: &str,
+    token: &SecretString,
     query: &str,
     types: &[ChannelType],
 ) -> Result<Vec<ChannelInfo>> {
@@ -190,8 +901,7 @@ pub fn search_channels(
             params.push(("cursor".to_string(), cursor.clone()));
         }
 
-        let mut resp = slack_post(token, "conversations.list")
-            .send_form(params)
+        let mut resp = send_form(token, "conversations.list", params)
             .context("failed to call conversations.list")?;
         let body: ConversationsListResponse = resp
             .body_mut()
@@ -212,6 +922,8 @@ pub fn search_channels(
                     channel_type: ch.channel_type(),
                     channel_id: ch.id.clone(),
                     user_id: ch.user.clone(),
+                    is_member: ch.is_member,
+                    is_shared: ch.is_shared_externally(),
                 });
             }
         }
@@ -229,3 +941,206 @@ pub fn search_channels(
     results.sort_by(|a, b| a.name.cmp(&b.name));
     Ok(results)
 }
+
+/// Whether `s` looks like a channel, private channel or DM ID (`C…`, `G…`, `D…`).
+pub fn is_conversation_id(s: &str) -> bool {
+    s.len() > 1
+        && s.starts_with(['C', 'G', 'D'])
+        && s.bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+/// Resolve a configured channel (`#name` or conversation ID) to a conversation ID.
+/// Most Web API methods other than chat.postMessage only accept IDs.
+pub fn resolve_channel_id(token: &SecretString, channel: &str) -> Result<String> {
+    let Some(name) = channel.strip_prefix('#') else {
+        return Ok(channel.to_string());
+    };
+    search_channels(token, name, &[ChannelType::PublicChannel])?
+        .into_iter()
+        .find(|c| c.name == name)
+        .map(|c| c.channel_id)
+        .with_context(|| {
+            format!("channel '{channel}' not found (use the channel ID for private channels)")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(file: &UploadFile) -> Vec<u8> {
+        let mut buf = Vec::new();
+        file.reader().unwrap().read_to_end(&mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn complete_upload_body_shares_into_thread() {
+        let body = CompleteUploadBody {
+            files: vec![FileEntry {
+                id: "F1".to_string(),
+                title: "build.log".to_string(),
+            }],
+            channel_id: Some("C1".to_string()),
+            initial_comment: None,
+            thread_ts: Some("1712345678.123456".to_string()),
+        };
+        assert_eq!(
+            serde_json::to_value(&body).unwrap(),
+            serde_json::json!({
+                "files": [{"id": "F1", "title": "build.log"}],
+                "channel_id": "C1",
+                "thread_ts": "1712345678.123456",
+            })
+        );
+    }
+
+    #[test]
+    fn message_request_matches_the_sent_body() {
+        let options = MessageOptions {
+            thread_ts: Some("1712345678.123456".to_string()),
+            unfurl_links: Some(false),
+            ..MessageOptions::default()
+        };
+        let (method, body) = message_request(&Delivery::Post, "#deploy", "hi", &options);
+        assert_eq!(method, "chat.postMessage");
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "channel": "#deploy",
+                "text": "hi",
+                "thread_ts": "1712345678.123456",
+                "unfurl_links": false,
+            })
+        );
+
+        let (method, body) = message_request(
+            &Delivery::Schedule {
+                post_at: 1_700_000_000,
+            },
+            "#deploy",
+            "hi",
+            &options,
+        );
+        assert_eq!(method, "chat.scheduleMessage");
+        assert_eq!(body["post_at"], 1_700_000_000);
+
+        let (method, body) = message_request(&Delivery::Me, "#deploy", "waves", &options);
+        assert_eq!(method, "chat.meMessage");
+        assert_eq!(
+            body,
+            serde_json::json!({"channel": "#deploy", "text": "waves"})
+        );
+    }
+
+    #[test]
+    fn upload_file_from_path_reads_lazily() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.log");
+        std::fs::write(&path, "hello").unwrap();
+        let file = UploadFile::from_path("a.log".to_string(), &path).unwrap();
+        assert_eq!(file.len, 5);
+        assert_eq!(read_all(&file), b"hello");
+    }
+
+    #[test]
+    fn spooled_upload_is_removed_on_drop() {
+        let file = UploadFile::spool("stdin".to_string(), &b"piped data"[..], 100).unwrap();
+        assert_eq!(file.len, 10);
+        assert_eq!(read_all(&file), b"piped data");
+        let UploadSource::Spool(path) = &file.source else {
+            panic!("expected a spool file");
+        };
+        let path = path.clone();
+        assert!(path.exists());
+        drop(file);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn upload_content_streams_with_content_length() {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/upload", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                headers.push(line.trim_end().to_ascii_lowercase());
+            }
+            let mut body = vec![0; 5];
+            reader.read_exact(&mut body).unwrap();
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                .unwrap();
+            (headers, body)
+        });
+
+        let file = UploadFile::from_bytes("a.txt".to_string(), b"hello".to_vec());
+        let reported = Mutex::new(Vec::new());
+        upload_file_content(&url, &file, &|sent| reported.lock().unwrap().push(sent)).unwrap();
+        let (headers, body) = server.join().unwrap();
+        assert!(headers.contains(&"content-length: 5".to_string()));
+        assert!(headers.contains(&"content-type: text/plain".to_string()));
+        assert!(!headers.iter().any(|h| h.starts_with("transfer-encoding")));
+        assert_eq!(body, b"hello");
+        assert_eq!(reported.into_inner().unwrap().last(), Some(&5));
+    }
+
+    #[test]
+    fn spool_gzip_compresses_and_renames() {
+        let text = "line\n".repeat(1000);
+        let file = UploadFile::spool_gzip("app.log".to_string(), text.as_bytes(), 1024).unwrap();
+        assert_eq!(file.name, "app.log.gz");
+        assert!(file.len < text.len() as u64);
+
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(file.reader().unwrap())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, text);
+    }
+
+    #[test]
+    fn sha256_of_upload_content() {
+        let file = UploadFile::from_bytes("a.txt".to_string(), b"abc".to_vec());
+        assert_eq!(
+            file.sha256().unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn spool_stops_just_past_the_limit() {
+        let file = UploadFile::spool("stdin".to_string(), &[0u8; 64][..], 10).unwrap();
+        assert_eq!(file.len, 11);
+    }
+
+    #[test]
+    fn transient_upload_errors() {
+        for e in ["file_not_found", "file_upload_not_complete"] {
+            assert!(
+                is_transient_upload_error(e),
+                "expected '{e}' to be transient"
+            );
+        }
+        for e in [
+            "invalid_auth",
+            "channel_not_found",
+            "not_in_channel",
+            "internal_error",
+            "service_unavailable",
+        ] {
+            assert!(!is_transient_upload_error(e), "expected '{e}' to be fatal");
+        }
+    }
+}
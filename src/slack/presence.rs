@@ -0,0 +1,110 @@
+use anyhow::{Context, Result};
+use secrecy::SecretString;
+use serde::Deserialize;
+
+use super::client::{check_ok, send_form};
+
+#[derive(Deserialize)]
+struct PresenceResponse {
+    ok: bool,
+    error: Option<String>,
+    presence: Option<String>,
+}
+
+/// `active` or `away` (needs `users:read`).
+pub fn get_presence(token: &SecretString, user: &str) -> Result<String> {
+    let mut resp = send_form(token, "users.getPresence", [("user", user)])
+        .context("failed to call users.getPresence")?;
+    let body: PresenceResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse users.getPresence response")?;
+    check_ok(body.ok, body.error.as_deref(), "users.getPresence")?;
+    body.presence.context("missing presence in response")
+}
+
+#[derive(Deserialize)]
+struct DndInfoResponse {
+    ok: bool,
+    error: Option<String>,
+    #[serde(flatten)]
+    info: DndInfo,
+}
+
+/// A user's do-not-disturb schedule and snooze state from dnd.info.
+#[derive(Debug, Default, Deserialize)]
+pub struct DndInfo {
+    #[serde(default)]
+    dnd_enabled: bool,
+    next_dnd_start_ts: Option<i64>,
+    next_dnd_end_ts: Option<i64>,
+    #[serde(default)]
+    snooze_enabled: bool,
+    snooze_endtime: Option<i64>,
+}
+
+impl DndInfo {
+    /// Whether notifications are paused at `now` (Unix seconds), by snooze or by schedule.
+    pub fn is_active(&self, now: i64) -> bool {
+        if self.snooze_enabled {
+            return true;
+        }
+        match (self.next_dnd_start_ts, self.next_dnd_end_ts) {
+            (Some(start), Some(end)) => self.dnd_enabled && start <= now && now < end,
+            _ => false,
+        }
+    }
+
+    /// When the current pause ends, if Slack reported it.
+    pub fn until(&self) -> Option<i64> {
+        if self.snooze_enabled {
+            self.snooze_endtime
+        } else {
+            self.next_dnd_end_ts
+        }
+    }
+}
+
+/// Do-not-disturb status for a user (needs `dnd:read`).
+pub fn dnd_info(token: &SecretString, user: &str) -> Result<DndInfo> {
+    let mut resp =
+        send_form(token, "dnd.info", [("user", user)]).context("failed to call dnd.info")?;
+    let body: DndInfoResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse dnd.info response")?;
+    check_ok(body.ok, body.error.as_deref(), "dnd.info")?;
+    Ok(body.info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(json: &str) -> DndInfo {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn scheduled_dnd_window() {
+        let dnd = info(r#"{"dnd_enabled":true,"next_dnd_start_ts":100,"next_dnd_end_ts":200}"#);
+        assert!(!dnd.is_active(99));
+        assert!(dnd.is_active(100));
+        assert!(dnd.is_active(199));
+        assert!(!dnd.is_active(200));
+        assert_eq!(dnd.until(), Some(200));
+    }
+
+    #[test]
+    fn disabled_schedule_is_inactive() {
+        let dnd = info(r#"{"dnd_enabled":false,"next_dnd_start_ts":100,"next_dnd_end_ts":200}"#);
+        assert!(!dnd.is_active(150));
+    }
+
+    #[test]
+    fn snooze_is_active() {
+        let dnd = info(r#"{"dnd_enabled":false,"snooze_enabled":true,"snooze_endtime":500}"#);
+        assert!(dnd.is_active(0));
+        assert_eq!(dnd.until(), Some(500));
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use secrecy::SecretString;
+use serde::Deserialize;
+
+use super::client::{check_ok, send_form, OkResponse};
+
+#[derive(Deserialize)]
+struct ReactionsGetResponse {
+    ok: bool,
+    error: Option<String>,
+    message: Option<ReactedItem>,
+}
+
+#[derive(Deserialize)]
+struct ReactedItem {
+    #[serde(default)]
+    reactions: Vec<Reaction>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Reaction {
+    pub name: String,
+    pub count: u32,
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+pub fn get_reactions(token: &SecretString, channel: &str, ts: &str) -> Result<Vec<Reaction>> {
+    let mut resp = send_form(
+        token,
+        "reactions.get",
+        [("channel", channel), ("timestamp", ts), ("full", "true")],
+    )
+    .context("failed to call reactions.get")?;
+    let body: ReactionsGetResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse reactions.get response")?;
+    check_ok(body.ok, body.error.as_deref(), "reactions.get")?;
+    Ok(body.message.map(|m| m.reactions).unwrap_or_default())
+}
+
+pub fn remove_reaction(token: &SecretString, channel: &str, ts: &str, name: &str) -> Result<()> {
+    let mut resp = send_form(
+        token,
+        "reactions.remove",
+        [("channel", channel), ("timestamp", ts), ("name", name)],
+    )
+    .context("failed to call reactions.remove")?;
+    let result: OkResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse reactions.remove response")?;
+    check_ok(result.ok, result.error.as_deref(), "reactions.remove")
+}
+
+/// Normalize an emoji argument (`:thumbsup:` or `thumbsup`) to the API's name form.
+pub fn emoji_name(emoji: &str) -> &str {
+    emoji.trim().trim_matches(':')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emoji_name_strips_colons() {
+        assert_eq!(emoji_name(":thumbsup:"), "thumbsup");
+        assert_eq!(emoji_name("eyes"), "eyes");
+        assert_eq!(emoji_name(" :+1: "), "+1");
+    }
+
+    #[test]
+    fn reactions_get_response_parses() {
+        let json = r#"{"ok":true,"type":"message","message":{"reactions":[{"name":"eyes","count":2,"users":["U1","U2"]}]}}"#;
+        let body: ReactionsGetResponse = serde_json::from_str(json).unwrap();
+        let reactions = body.message.unwrap().reactions;
+        assert_eq!(
+            reactions,
+            vec![Reaction {
+                name: "eyes".to_string(),
+                count: 2,
+                users: vec!["U1".to_string(), "U2".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn reactions_get_response_without_reactions() {
+        let json = r#"{"ok":true,"type":"message","message":{"text":"hi"}}"#;
+        let body: ReactionsGetResponse = serde_json::from_str(json).unwrap();
+        assert!(body.message.unwrap().reactions.is_empty());
+    }
+}
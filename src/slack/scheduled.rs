@@ -0,0 +1,102 @@
+use anyhow::{Context, Result};
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+
+use super::client::{check_ok, send_form, OkResponse};
+
+#[derive(Deserialize)]
+struct ScheduledListResponse {
+    ok: bool,
+    error: Option<String>,
+    #[serde(default)]
+    scheduled_messages: Vec<ScheduledMessageInfo>,
+    response_metadata: Option<ResponseMetadata>,
+}
+
+#[derive(Deserialize)]
+struct ResponseMetadata {
+    next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledMessageInfo {
+    pub id: String,
+    pub channel_id: String,
+    /// Unix seconds
+    pub post_at: i64,
+    #[serde(default)]
+    pub text: String,
+}
+
+/// List pending scheduled messages in a channel (conversation ID), soonest first.
+pub fn list_scheduled(token: &SecretString, channel: &str) -> Result<Vec<ScheduledMessageInfo>> {
+    let mut results = Vec::new();
+    let mut cursor = String::new();
+
+    loop {
+        let mut params = vec![("channel", channel), ("limit", "100")];
+        if !cursor.is_empty() {
+            params.push(("cursor", &cursor));
+        }
+        let mut resp = send_form(token, "chat.scheduledMessages.list", params)
+            .context("failed to call chat.scheduledMessages.list")?;
+        let body: ScheduledListResponse = resp
+            .body_mut()
+            .read_json()
+            .context("failed to parse chat.scheduledMessages.list response")?;
+        check_ok(
+            body.ok,
+            body.error.as_deref(),
+            "chat.scheduledMessages.list",
+        )?;
+        results.extend(body.scheduled_messages);
+
+        match body.response_metadata.and_then(|m| m.next_cursor) {
+            Some(next) if !next.is_empty() => cursor = next,
+            _ => break,
+        }
+    }
+
+    results.sort_by_key(|m| m.post_at);
+    Ok(results)
+}
+
+pub fn delete_scheduled(token: &SecretString, channel: &str, id: &str) -> Result<()> {
+    let mut resp = send_form(
+        token,
+        "chat.deleteScheduledMessage",
+        [("channel", channel), ("scheduled_message_id", id)],
+    )
+    .context("failed to call chat.deleteScheduledMessage")?;
+    let result: OkResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse chat.deleteScheduledMessage response")?;
+    check_ok(
+        result.ok,
+        result.error.as_deref(),
+        "chat.deleteScheduledMessage",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_list_response() {
+        let body: ScheduledListResponse = serde_json::from_str(
+            r#"{"ok":true,"scheduled_messages":[{"id":"Q1","channel_id":"C1","post_at":1700000000,"date_created":1690000000,"text":"hi"}],"response_metadata":{"next_cursor":""}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            body.scheduled_messages,
+            vec![ScheduledMessageInfo {
+                id: "Q1".to_string(),
+                channel_id: "C1".to_string(),
+                post_at: 1700000000,
+                text: "hi".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use anyhow::{Context, Result};
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+
+use super::client::{check_ok, send_form};
+
+#[derive(Deserialize)]
+struct UsergroupsListResponse {
+    ok: bool,
+    error: Option<String>,
+    #[serde(default)]
+    usergroups: Vec<Usergroup>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Usergroup {
+    pub id: String,
+    pub handle: String,
+    pub name: String,
+}
+
+impl Usergroup {
+    /// Markup that mentions the group, for templates and block payloads.
+    pub fn mention(&self) -> String {
+        format!("<!subteam^{}>", self.id)
+    }
+}
+
+/// The workspace's enabled user groups, sorted by handle (needs `usergroups:read`).
+pub fn list_usergroups(token: &SecretString) -> Result<Vec<Usergroup>> {
+    let mut resp = send_form(token, "usergroups.list", [("include_disabled", "false")])
+        .context("failed to call usergroups.list")?;
+    let body: UsergroupsListResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse usergroups.list response")?;
+    check_ok(body.ok, body.error.as_deref(), "usergroups.list")?;
+    let mut groups = body.usergroups;
+    groups.sort_by(|a, b| a.handle.cmp(&b.handle));
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_usergroups_list() {
+        let body: UsergroupsListResponse = serde_json::from_str(
+            r#"{"ok":true,"usergroups":[{"id":"S0614TZR7","handle":"oncall","name":"On-call","is_external":false}]}"#,
+        )
+        .unwrap();
+        let group = &body.usergroups[0];
+        assert_eq!(group.handle, "oncall");
+        assert_eq!(group.mention(), "<!subteam^S0614TZR7>");
+    }
+}
@@ -0,0 +1,193 @@
+use anyhow::{bail, Context, Result};
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+
+use super::client::{check_ok, send_form};
+
+#[derive(Deserialize)]
+struct LookupByEmailResponse {
+    ok: bool,
+    error: Option<String>,
+    user: Option<User>,
+}
+
+#[derive(Deserialize)]
+struct UsersListResponse {
+    ok: bool,
+    error: Option<String>,
+    #[serde(default)]
+    members: Vec<User>,
+    response_metadata: Option<ResponseMetadata>,
+}
+
+#[derive(Deserialize)]
+struct ResponseMetadata {
+    next_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct User {
+    id: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    deleted: bool,
+    #[serde(default)]
+    profile: UserProfile,
+}
+
+#[derive(Default, Deserialize)]
+struct UserProfile {
+    #[serde(default)]
+    display_name: String,
+}
+
+impl User {
+    fn matches_handle(&self, handle: &str) -> bool {
+        !self.deleted
+            && (self.name.eq_ignore_ascii_case(handle)
+                || self.profile.display_name.eq_ignore_ascii_case(handle))
+    }
+}
+
+/// Whether a string already looks like a user ID (`U…` / `W…`).
+pub fn is_user_id(s: &str) -> bool {
+    s.len() > 1
+        && (s.starts_with('U') || s.starts_with('W'))
+        && s.bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+/// Resolve a user given as an ID, an email address, or an `@handle` to a user ID.
+pub fn resolve_user_id(token: &SecretString, user: &str) -> Result<String> {
+    let user = user.trim();
+    if is_user_id(user) {
+        return Ok(user.to_string());
+    }
+    if user.contains('@') && !user.starts_with('@') {
+        return lookup_by_email(token, user);
+    }
+    let handle = user.strip_prefix('@').unwrap_or(user);
+    if handle.is_empty() {
+        bail!("user must not be empty");
+    }
+    find_by_handle(token, handle)
+}
+
+/// Look up a member by email via users.lookupByEmail (needs `users:read.email`).
+pub fn lookup_member_by_email(token: &SecretString, email: &str) -> Result<Member> {
+    let mut resp = send_form(token, "users.lookupByEmail", [("email", email)])
+        .context("failed to call users.lookupByEmail")?;
+    let body: LookupByEmailResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse users.lookupByEmail response")?;
+    if body.error.as_deref() == Some("users_not_found") {
+        bail!("no Slack user found with email '{email}'");
+    }
+    check_ok(body.ok, body.error.as_deref(), "users.lookupByEmail")?;
+    let user = body.user.context("missing user in response")?;
+    Ok(Member {
+        id: user.id,
+        name: user.name,
+        display_name: user.profile.display_name,
+    })
+}
+
+fn lookup_by_email(token: &SecretString, email: &str) -> Result<String> {
+    Ok(lookup_member_by_email(token, email)?.id)
+}
+
+fn users_page(token: &SecretString, cursor: &str) -> Result<(Vec<User>, Option<String>)> {
+    let mut params = vec![("limit", "200")];
+    if !cursor.is_empty() {
+        params.push(("cursor", cursor));
+    }
+    let mut resp = send_form(token, "users.list", params).context("failed to call users.list")?;
+    let body: UsersListResponse = resp
+        .body_mut()
+        .read_json()
+        .context("failed to parse users.list response")?;
+    check_ok(body.ok, body.error.as_deref(), "users.list")?;
+    let next = body
+        .response_metadata
+        .and_then(|m| m.next_cursor)
+        .filter(|c| !c.is_empty());
+    Ok((body.members, next))
+}
+
+fn find_by_handle(token: &SecretString, handle: &str) -> Result<String> {
+    let mut cursor = String::new();
+    loop {
+        let (members, next) = users_page(token, &cursor)?;
+        if let Some(user) = members.iter().find(|u| u.matches_handle(handle)) {
+            return Ok(user.id.clone());
+        }
+        match next {
+            Some(next) => cursor = next,
+            None => bail!("user '@{handle}' not found"),
+        }
+    }
+}
+
+/// A workspace member's ID and the handles they can be mentioned by.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Member {
+    pub id: String,
+    pub name: String,
+    pub display_name: String,
+}
+
+/// All active (non-deleted) members of the workspace.
+pub fn list_members(token: &SecretString) -> Result<Vec<Member>> {
+    let mut members = Vec::new();
+    let mut cursor = String::new();
+    loop {
+        let (page, next) = users_page(token, &cursor)?;
+        members.extend(page.into_iter().filter(|u| !u.deleted).map(|u| Member {
+            id: u.id,
+            name: u.name,
+            display_name: u.profile.display_name,
+        }));
+        match next {
+            Some(next) => cursor = next,
+            None => return Ok(members),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("U0123ABCD", true)]
+    #[case("W0123ABCD", true)]
+    #[case("U", false)]
+    #[case("alice", false)]
+    #[case("Uabc", false)]
+    #[case("C0123ABCD", false)]
+    fn user_id_shape(#[case] input: &str, #[case] expected: bool) {
+        assert_eq!(is_user_id(input), expected);
+    }
+
+    #[test]
+    fn matches_handle_by_name_or_display_name() {
+        let user: User = serde_json::from_str(
+            r#"{"id":"U1","name":"alice.s","profile":{"display_name":"Alice"}}"#,
+        )
+        .unwrap();
+        assert!(user.matches_handle("alice.s"));
+        assert!(user.matches_handle("alice"));
+        assert!(!user.matches_handle("bob"));
+    }
+
+    #[test]
+    fn deleted_users_never_match() {
+        let user: User =
+            serde_json::from_str(r#"{"id":"U1","name":"alice","deleted":true}"#).unwrap();
+        assert!(!user.matches_handle("alice"));
+    }
+}
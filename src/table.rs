@@ -0,0 +1,182 @@
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+
+/// Input delimiter for `--table`.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Delimiter {
+    Tsv,
+    Csv,
+}
+
+impl Delimiter {
+    /// Guess from the first line: tabs mean TSV, otherwise CSV.
+    pub fn detect(input: &str) -> Self {
+        match input.lines().next() {
+            Some(line) if line.contains('\t') => Self::Tsv,
+            _ => Self::Csv,
+        }
+    }
+}
+
+/// Split delimiter-separated text into rows. CSV supports double-quoted fields with `""` escapes.
+pub fn parse_rows(input: &str, delimiter: Delimiter) -> Result<Vec<Vec<String>>> {
+    let rows = match delimiter {
+        Delimiter::Tsv => input
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.split('\t').map(|c| c.trim().to_string()).collect())
+            .collect(),
+        Delimiter::Csv => parse_csv(input)?,
+    };
+    if rows.is_empty() {
+        bail!("table input is empty");
+    }
+    Ok(rows)
+}
+
+fn parse_csv(input: &str) -> Result<Vec<Vec<String>>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => row.push(std::mem::take(&mut field).trim().to_string()),
+            ('\r', false) => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field).trim().to_string());
+                let done = std::mem::take(&mut row);
+                if !(done.len() == 1 && done[0].is_empty()) {
+                    rows.push(done);
+                }
+            }
+            (c, _) => field.push(c),
+        }
+    }
+    if in_quotes {
+        bail!("unterminated quoted field in CSV input");
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field.trim().to_string());
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Align columns with two-space gutters and underline the first (header) row.
+pub fn render(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|r| r.get(i))
+                .map(|c| c.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let line = |row: &[String]| {
+        let cells: Vec<String> = (0..columns)
+            .map(|i| {
+                let cell = row.get(i).map(String::as_str).unwrap_or("");
+                format!("{cell:<width$}", width = widths[i])
+            })
+            .collect();
+        cells.join("  ").trim_end().to_string()
+    };
+
+    let mut out = vec![line(&rows[0])];
+    if rows.len() > 1 {
+        let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+        out.push(rule.join("  "));
+    }
+    out.extend(rows[1..].iter().map(|r| line(r)));
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(input: &[&[&str]]) -> Vec<Vec<String>> {
+        input
+            .iter()
+            .map(|r| r.iter().map(|c| c.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn detect_delimiter() {
+        assert_eq!(Delimiter::detect("a\tb\n1\t2"), Delimiter::Tsv);
+        assert_eq!(Delimiter::detect("a,b\n1,2"), Delimiter::Csv);
+    }
+
+    #[test]
+    fn parse_tsv_skips_blank_lines() {
+        let parsed = parse_rows("name\tcount\n\nfoo\t3\n", Delimiter::Tsv).unwrap();
+        assert_eq!(parsed, rows(&[&["name", "count"], &["foo", "3"]]));
+    }
+
+    #[test]
+    fn parse_csv_handles_quotes() {
+        let parsed = parse_rows(
+            "name,note\r\n\"Smith, J\",\"said \"\"hi\"\"\"\nfoo,\"multi\nline\"\n",
+            Delimiter::Csv,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed,
+            rows(&[
+                &["name", "note"],
+                &["Smith, J", "said \"hi\""],
+                &["foo", "multi\nline"]
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_csv_without_trailing_newline() {
+        let parsed = parse_rows("a,b\n1,", Delimiter::Csv).unwrap();
+        assert_eq!(parsed, rows(&[&["a", "b"], &["1", ""]]));
+    }
+
+    #[test]
+    fn parse_csv_rejects_unterminated_quote() {
+        assert!(parse_rows("a,\"b\n", Delimiter::Csv).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_empty_input() {
+        assert!(parse_rows("\n\n", Delimiter::Tsv).is_err());
+    }
+
+    #[test]
+    fn render_aligns_columns() {
+        let table = render(&rows(&[
+            &["name", "count"],
+            &["foo", "3"],
+            &["longer-name", "12", "extra"],
+        ]));
+        assert_eq!(
+            table,
+            "name         count\n\
+             -----------  -----  -----\n\
+             foo          3\n\
+             longer-name  12     extra"
+        );
+    }
+
+    #[test]
+    fn render_single_row_has_no_rule() {
+        assert_eq!(render(&rows(&[&["a", "b"]])), "a  b");
+    }
+}
@@ -0,0 +1,96 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// When a profile last sent, for `min_interval`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Entry {
+    /// Profile used (`None` = default)
+    profile: Option<String>,
+    /// Unix time of the send
+    sent_at: i64,
+}
+
+pub fn state_path() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir().context("could not determine data directory")?;
+    Ok(data_dir.join("slafling").join("last_send.json"))
+}
+
+fn read(path: &Path) -> Result<Vec<Entry>> {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+    };
+    serde_json::from_str(&json)
+        .with_context(|| format!("{}: invalid last-send state", path.display()))
+}
+
+/// When the profile may send again, if `now` is less than `interval_secs` after its last send.
+pub fn next_allowed(
+    path: &Path,
+    profile: Option<&str>,
+    now: i64,
+    interval_secs: i64,
+) -> Result<Option<i64>> {
+    Ok(read(path)?
+        .into_iter()
+        .find(|e| e.profile.as_deref() == profile)
+        .map(|e| e.sent_at + interval_secs)
+        .filter(|&next| next > now))
+}
+
+/// Remember that the profile sent at `now`.
+pub fn record(path: &Path, profile: Option<&str>, now: i64) -> Result<()> {
+    let mut entries: Vec<Entry> = read(path)?
+        .into_iter()
+        .filter(|e| e.profile.as_deref() != profile)
+        .collect();
+    entries.push(Entry {
+        profile: profile.map(String::from),
+        sent_at: now,
+    });
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+    // Write then rename, so a concurrent run never reads a half-written file
+    let tmp = path.with_extension(format!("json.{}", std::process::id()));
+    std::fs::write(&tmp, serde_json::to_string(&entries)?)
+        .with_context(|| format!("failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_allowed_after_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("slafling").join("last_send.json");
+        assert_eq!(next_allowed(&path, None, 1000, 30).unwrap(), None);
+
+        record(&path, None, 1000).unwrap();
+        assert_eq!(next_allowed(&path, None, 1010, 30).unwrap(), Some(1030));
+        assert_eq!(next_allowed(&path, None, 1030, 30).unwrap(), None);
+    }
+
+    #[test]
+    fn profiles_are_tracked_separately() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("last_send.json");
+        record(&path, Some("ci"), 1000).unwrap();
+        record(&path, None, 900).unwrap();
+        record(&path, Some("ci"), 1100).unwrap();
+        assert_eq!(read(&path).unwrap().len(), 2);
+        assert_eq!(
+            next_allowed(&path, Some("ci"), 1101, 30).unwrap(),
+            Some(1130)
+        );
+        assert_eq!(next_allowed(&path, None, 1101, 30).unwrap(), None);
+        assert_eq!(next_allowed(&path, Some("ops"), 1101, 30).unwrap(), None);
+    }
+}
@@ -1,13 +1,15 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
+use secrecy::zeroize::Zeroizing;
+use secrecy::{ExposeSecret, SecretString};
 
 fn token_dir() -> Result<PathBuf> {
     let data_dir = dirs::data_dir().context("could not determine data directory")?;
     Ok(data_dir.join("slafling").join("tokens"))
 }
 
-fn validate_profile_name(name: &str) -> Result<()> {
+pub fn validate_profile_name(name: &str) -> Result<()> {
     if name.is_empty()
         || name.contains('/')
         || name.contains('\\')
@@ -29,14 +31,15 @@ pub fn token_path(profile: Option<&str>) -> Result<PathBuf> {
     profile_path(&token_dir()?, profile)
 }
 
-fn read_token(path: &Path) -> Result<Option<String>> {
+fn read_token(path: &Path) -> Result<Option<SecretString>> {
     match std::fs::read_to_string(path) {
         Ok(content) => {
-            let token = content.trim().to_string();
+            let content = Zeroizing::new(content);
+            let token = content.trim();
             if token.is_empty() {
                 Ok(None)
             } else {
-                Ok(Some(token))
+                Ok(Some(token.into()))
             }
         }
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
@@ -44,7 +47,7 @@ fn read_token(path: &Path) -> Result<Option<String>> {
     }
 }
 
-fn write_token(path: &Path, token: &str) -> Result<()> {
+fn write_token(path: &Path, token: &SecretString) -> Result<()> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)
             .with_context(|| format!("failed to create directory {}", parent.display()))?;
@@ -62,7 +65,7 @@ fn write_token(path: &Path, token: &str) -> Result<()> {
             .mode(0o600)
             .open(path)
             .with_context(|| format!("failed to create token file {}", path.display()))?;
-        file.write_all(token.as_bytes())
+        file.write_all(token.expose_secret().as_bytes())
             .with_context(|| format!("failed to write token file {}", path.display()))?;
         // Ensure permissions are 0o600 even when overwriting an existing file
         std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
@@ -71,7 +74,7 @@ fn write_token(path: &Path, token: &str) -> Result<()> {
 
     #[cfg(not(unix))]
     {
-        std::fs::write(path, token)
+        std::fs::write(path, token.expose_secret())
             .with_context(|| format!("failed to write token file {}", path.display()))?;
     }
 
@@ -86,11 +89,11 @@ fn remove_token(path: &Path) -> Result<()> {
     }
 }
 
-pub fn get_token(profile: Option<&str>) -> Result<Option<String>> {
+pub fn get_token(profile: Option<&str>) -> Result<Option<SecretString>> {
     read_token(&token_path(profile)?)
 }
 
-pub fn set_token(profile: Option<&str>, token: &str) -> Result<()> {
+pub fn set_token(profile: Option<&str>, token: &SecretString) -> Result<()> {
     write_token(&token_path(profile)?, token)
 }
 
@@ -126,12 +129,15 @@ mod tests {
         let path = profile_path(&tokens, Some("test-profile")).unwrap();
 
         // write
-        write_token(&path, "xoxb-test-123").unwrap();
+        write_token(&path, &"xoxb-test-123".into()).unwrap();
         assert!(path.exists());
 
         // read
         let token = read_token(&path).unwrap();
-        assert_eq!(token, Some("xoxb-test-123".to_string()));
+        assert_eq!(
+            token.as_ref().map(|t| t.expose_secret()),
+            Some("xoxb-test-123")
+        );
 
         // delete
         remove_token(&path).unwrap();
@@ -139,7 +145,7 @@ mod tests {
 
         // read after delete
         let token = read_token(&path).unwrap();
-        assert_eq!(token, None);
+        assert!(token.is_none());
     }
 
     #[cfg(unix)]
@@ -150,7 +156,7 @@ mod tests {
         let (_dir, tokens) = test_dir();
         let path = profile_path(&tokens, Some("perm-test")).unwrap();
 
-        write_token(&path, "xoxb-secret").unwrap();
+        write_token(&path, &"xoxb-secret".into()).unwrap();
         let mode = std::fs::metadata(&path).unwrap().permissions().mode();
         assert_eq!(mode & 0o777, 0o600);
     }
@@ -164,11 +170,11 @@ mod tests {
         let path = profile_path(&tokens, Some("overwrite-test")).unwrap();
 
         // Create file with loose permissions
-        write_token(&path, "initial").unwrap();
+        write_token(&path, &"initial".into()).unwrap();
         std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
 
         // Overwrite — should restore 0o600
-        write_token(&path, "updated").unwrap();
+        write_token(&path, &"updated".into()).unwrap();
         let mode = std::fs::metadata(&path).unwrap().permissions().mode();
         assert_eq!(mode & 0o777, 0o600);
     }
@@ -177,15 +183,15 @@ mod tests {
     fn read_token_missing_file_returns_none() {
         let (_dir, tokens) = test_dir();
         let path = profile_path(&tokens, Some("nonexistent")).unwrap();
-        assert_eq!(read_token(&path).unwrap(), None);
+        assert!(read_token(&path).unwrap().is_none());
     }
 
     #[test]
     fn read_token_empty_file_returns_none() {
         let (_dir, tokens) = test_dir();
         let path = profile_path(&tokens, Some("empty")).unwrap();
-        write_token(&path, "").unwrap();
-        assert_eq!(read_token(&path).unwrap(), None);
+        write_token(&path, &"".into()).unwrap();
+        assert!(read_token(&path).unwrap().is_none());
     }
 
     #[test]